@@ -0,0 +1,85 @@
+/*
+* how long the solver's hot paths take on the real word lists.
+* run with 'cargo bench', criterion keeps the last run in target/criterion and reports changes against it.
+*/
+
+#![allow(clippy::needless_return)]
+
+use std::collections::HashMap;
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use wordlehelper::alphabet::Alphabet;
+use wordlehelper::automated;
+use wordlehelper::game;
+use wordlehelper::game::{Feedback, Pools};
+use wordlehelper::player;
+use wordlehelper::player::Strategy;
+use wordlehelper::prior::Prior;
+use wordlehelper::wordlist;
+use wordlehelper::wordlist::{Duplicates, WordList};
+
+const WORDS: &str = include_str!("../../words/wordle_words.txt");
+const ANSWERS: &str = include_str!("../../words/ny_times_answers.txt");
+
+// the puzzle the single game and the mid game benches use
+const DAY: usize = 100;
+const OPENER: &str = "roate";
+
+fn lists() -> (WordList, WordList) {
+    let word_list = wordlist::parse("words", WORDS, Duplicates::Drop, &Alphabet::english());
+    let answer_list = wordlist::parse("answers", ANSWERS, Duplicates::Keep, &word_list.alphabet);
+    return (word_list, answer_list);
+}
+
+fn solver(c: &mut Criterion) {
+    let (word_list, answer_list) = lists();
+    let words: Vec<&str> = word_list.words.iter().map(|word| word.as_str()).collect();
+    let answers: Vec<&str> = answer_list.words.iter().map(|word| word.as_str()).collect();
+    let pools = Pools::new(&words, &answers, Prior::uniform(), Feedback::Colors);
+    let packed = &pools.packed;
+
+    // the board after the opener on DAY, for the benches that look at a game in progress
+    let mut board_state: HashMap<String,Vec<u8>> = HashMap::new();
+    board_state.insert(OPENER.to_string(), game::determine_board_results(answers[DAY], OPENER));
+    let candidates = game::filter_candidates(&pools.possible, OPENER, &board_state[OPENER]);
+
+    c.bench_function("determine_board_results every answer", |b| b.iter(|| {
+        for answer in answers.iter() {
+            black_box(game::determine_board_results(black_box(answer), black_box(OPENER)));
+        }
+    }));
+
+    c.bench_function("get_letter_frequencies", |b| b.iter(|| {
+        black_box(player::get_letter_frequencies(&packed.letters, &packed.possible, packed.word_len, black_box(&board_state)))
+    }));
+
+    let letter_dist = player::get_letter_frequencies(&packed.letters, &packed.possible, packed.word_len, &board_state);
+    c.bench_function("get_distance_list", |b| b.iter(|| {
+        black_box(player::get_distance_list(black_box(&letter_dist)))
+    }));
+
+    let distance_lists = player::get_distance_list(&letter_dist);
+    let required_letters = player::build_required_list(&packed.letters, &board_state);
+    c.bench_function("suggest_word", |b| b.iter(|| {
+        black_box(player::suggest_word(&packed.allowed, &candidates, black_box(&distance_lists), packed.word_len, required_letters, false))
+    }));
+
+    c.bench_function("one game", |b| b.iter(|| {
+        black_box(automated::play(&pools, &answers, DAY, Strategy::Frequency, &mut None, true))
+    }));
+
+    let mut group = c.benchmark_group("full answer list");
+    group.sample_size(10);
+    group.bench_function("frequency", |b| b.iter(|| {
+        let mut opener = None;
+        for day in 0..answers.len() {
+            black_box(automated::play(&pools, &answers, day, Strategy::Frequency, &mut opener, true));
+        }
+    }));
+    group.finish();
+}
+
+criterion_group!(benches, solver);
+criterion_main!(benches);
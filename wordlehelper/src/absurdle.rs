@@ -0,0 +1,177 @@
+/*
+* absurdle, wordle with a host that cheats. there's no answer picked up front. every guess splits the
+* candidates into buckets by the result they'd give, and the host answers with the biggest bucket so
+* the game drags on as long as possible. the game is over when the guess is the only word left.
+*/
+
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+use serde_json::json;
+
+use crate::alphabet;
+use crate::game;
+use crate::game::{Feedback, Pools};
+use crate::output;
+use crate::player;
+use crate::player::Strategy;
+use crate::practice;
+use crate::render;
+
+// the solver gives up after this many guesses, the frequency strategy isn't made for a host like this
+const SOLVER_LIMIT: usize = 20;
+// the host never surprises anyone, so the minimax solver plans whole games ahead. it only tries the
+// few best guesses by worst case at each step, and plans up to this many guesses before settling for greedy
+const BEAM: usize = 10;
+const PLAN_DEPTH: usize = 6;
+
+// the host's answer to a guess and the candidates it keeps.
+// equal buckets go to the one that gives away the least, fewest greens and then fewest yellows.
+pub fn host_results<'a>(candidates: &[&'a str], guess_word: &str) -> (Vec<u8>, Vec<&'a str>) {
+    let word_len = alphabet::letter_count(guess_word);
+    let sizes = game::bucket_sizes(Feedback::Colors, candidates, guess_word);
+    let code = (0..sizes.len())
+        .filter(|code| sizes[*code] > 0)
+        .max_by_key(|code| {
+            let state_vec = game::decode_pattern(*code as u16, word_len);
+            let greens = state_vec.iter().filter(|state| **state == 2).count();
+            let yellows = state_vec.iter().filter(|state| **state == 1).count();
+            (sizes[*code], Reverse(greens), Reverse(yellows))
+        });
+    return match code {
+        Some(code) => {
+            let state_vec = game::decode_pattern(code as u16, word_len);
+            let remaining = game::filter_candidates(candidates, guess_word, &state_vec);
+            (state_vec, remaining)
+        }
+        // no candidates left at all, nothing the guess could match
+        None => (vec![0; word_len], Vec::new()),
+    };
+}
+
+// play the solver against the host. returns every guess and its results, the last one is solved
+// unless the solver ran out of ideas.
+pub fn solve(pools: &Pools, strategy: Strategy) -> Vec<(String,Vec<u8>)> {
+    if let Strategy::Minimax = strategy {
+        if let Some(guesses) = plan(pools, &pools.possible, PLAN_DEPTH) {
+            return replay(&pools.possible, &guesses);
+        }
+    }
+
+    let mut board_state: HashMap<String,Vec<u8>> = HashMap::new();
+    let mut history: Vec<(String,Vec<u8>)> = Vec::new();
+    let mut candidates: Vec<&str> = pools.possible.clone();
+    while history.len() < SOLVER_LIMIT {
+        let guess_word = player::best_guess(strategy, pools, &candidates, &board_state, &[], history.len());
+        if guess_word.is_empty() {
+            break
+        }
+        let (state_vec, remaining) = host_results(&candidates, &guess_word);
+        candidates = remaining;
+        history.push((guess_word.clone(), state_vec.clone()));
+        if game::is_solved(&state_vec) {
+            break
+        }
+        board_state.insert(guess_word, state_vec);
+    }
+    return history;
+}
+
+// the shortest run of guesses that solves within depth, only looking at the beam of best guesses each step
+fn plan(pools: &Pools, candidates: &[&str], depth: usize) -> Option<Vec<String>> {
+    if depth == 0 || candidates.is_empty() {
+        return None;
+    }
+    if candidates.len() == 1 {
+        return Some(vec![candidates[0].to_string()]);
+    }
+    let mut best: Option<Vec<String>> = None;
+    for guess_word in beam(pools, candidates) {
+        let (state_vec, remaining) = host_results(candidates, guess_word);
+        let rest = if game::is_solved(&state_vec) {
+            Some(Vec::new())
+        }
+        else {
+            // no point looking further than the best plan so far
+            let limit = best.as_ref().map(|best| best.len() - 1).unwrap_or(depth);
+            plan(pools, &remaining, limit.min(depth).saturating_sub(1))
+        };
+        if let Some(rest) = rest {
+            let mut guesses = vec![guess_word.to_string()];
+            guesses.extend(rest);
+            best = Some(guesses);
+        }
+    }
+    return best;
+}
+
+// the guesses with the smallest worst case. candidates and then alphabetical order break ties so plans repeat
+fn beam<'a>(pools: &Pools<'a>, candidates: &[&str]) -> Vec<&'a str> {
+    let mut scored: Vec<(f64, bool, &str)> = pools.allowed.iter()
+        .map(|guess_word| (player::minimax_score(Feedback::Colors, candidates, guess_word), candidates.contains(guess_word), *guess_word))
+        .collect();
+    scored.sort_by(|a, b| b.0.total_cmp(&a.0).then(b.1.cmp(&a.1)).then(a.2.cmp(b.2)));
+    return scored.iter().take(BEAM).map(|(_, _, guess_word)| *guess_word).collect();
+}
+
+// play planned guesses against the host to get their results
+fn replay(candidates: &[&str], guesses: &[String]) -> Vec<(String,Vec<u8>)> {
+    let mut candidates: Vec<&str> = candidates.to_vec();
+    let mut history: Vec<(String,Vec<u8>)> = Vec::new();
+    for guess_word in guesses.iter() {
+        let (state_vec, remaining) = host_results(&candidates, guess_word);
+        candidates = remaining;
+        history.push((guess_word.clone(), state_vec));
+    }
+    return history;
+}
+
+// you guess against the host, then see how the solver does it
+pub fn play(pools: &Pools, strategy: Strategy) {
+    output::say("absurdle. the host hasn't picked an answer and will dodge your guesses for as long as it can.");
+    let mut history: Vec<(String,Vec<u8>)> = Vec::new();
+    let mut candidates: Vec<&str> = pools.possible.clone();
+    while let Some(guess_word) = practice::read_guess(&pools.allowed) {
+        let (state_vec, remaining) = host_results(&candidates, &guess_word);
+        output::turn(pools, json!({ "player": "you" }), history.len() + 1, &candidates, &guess_word, &state_vec, remaining.len());
+        candidates = remaining;
+        if output::is_text() {
+            println!("{} {} left", render::tiles(&guess_word, &state_vec), candidates.len());
+        }
+        history.push((guess_word, state_vec.clone()));
+        if game::is_solved(&state_vec) {
+            output::say(&format!("solved in {} guesses.", history.len()));
+            break
+        }
+    }
+    record_game("you", &history);
+
+    let solver_history = solve(pools, strategy);
+    output::say("the solver's game:");
+    print_game(pools, &solver_history);
+}
+
+pub fn print_game(pools: &Pools, history: &[(String,Vec<u8>)]) {
+    if !output::is_text() {
+        // replay the game for the candidates each guess was played on
+        let mut candidates: Vec<&str> = pools.possible.clone();
+        for (turn, (guess_word, state_vec)) in history.iter().enumerate() {
+            let remaining = game::filter_candidates(&candidates, guess_word, state_vec);
+            output::turn(pools, json!({ "player": "solver" }), turn + 1, &candidates, guess_word, state_vec, remaining.len());
+            candidates = remaining;
+        }
+        record_game("solver", history);
+        return;
+    }
+    println!("{}", render::board(Feedback::Colors, history));
+    match history.last() {
+        Some((_, state_vec)) if game::is_solved(state_vec) => println!("solved in {} guesses.", history.len()),
+        _ => println!("not solved after {} guesses.", history.len()),
+    }
+}
+
+fn record_game(player: &str, history: &[(String,Vec<u8>)]) {
+    let solved = history.last().is_some_and(|(_, state_vec)| game::is_solved(state_vec));
+    let words: Vec<&str> = history.iter().map(|(guess_word, _)| guess_word.as_str()).collect();
+    output::record("game", json!({ "player": player, "solved": solved, "guesses": history.len(), "words": words }));
+}
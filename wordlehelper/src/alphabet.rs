@@ -0,0 +1,163 @@
+/*
+* letters for games in other languages. a letter is a grapheme, what a reader sees as one letter,
+* so 'ñ' is one letter even when it's typed as 'n' plus a combining tilde.
+* words are put in NFC form and lowercased so the same letter always looks the same,
+* and positions in a word are counted in letters, not bytes or chars.
+*/
+
+use unicode_normalization::UnicodeNormalization;
+use unicode_segmentation::UnicodeSegmentation;
+
+pub const ENGLISH: &str = "abcdefghijklmnopqrstuvwxyz";
+
+// the letters a word list can use
+#[derive(Clone)]
+pub struct Alphabet {
+    letters: Vec<String>,
+}
+
+impl Alphabet {
+    pub fn english() -> Alphabet {
+        return Alphabet::parse(ENGLISH);
+    }
+
+    // every letter in the text, spaces and commas between them are fine
+    pub fn parse(text: &str) -> Alphabet {
+        let normalized = normalize(text);
+        let mut letters: Vec<String> = Vec::new();
+        for letter in normalized.graphemes(true) {
+            if letter.trim().is_empty() || letter == "," || letters.iter().any(|known| known == letter) {
+                continue
+            }
+            letters.push(letter.to_string());
+        }
+        return Alphabet { letters };
+    }
+
+    pub fn contains(&self, letter: &str) -> bool {
+        return self.letters.iter().any(|known| known == letter);
+    }
+
+    // the letters of a word that aren't in the alphabet
+    pub fn unknown_letters<'a>(&self, word: &'a str) -> Vec<&'a str> {
+        return letters(word).into_iter().filter(|letter| !self.contains(letter)).collect();
+    }
+}
+
+// one way of writing each letter, lowercase
+pub fn normalize(text: &str) -> String {
+    return text.nfc().collect::<String>().to_lowercase();
+}
+
+// the letters of a word in order
+pub fn letters(word: &str) -> Vec<&str> {
+    if word.is_ascii() {
+        return (0..word.len()).map(|i| &word[i..i + 1]).collect();
+    }
+    return word.graphemes(true).collect();
+}
+
+// how many letters long a word is
+pub fn letter_count(word: &str) -> usize {
+    if word.is_ascii() {
+        return word.len();
+    }
+    return word.graphemes(true).count();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game;
+    use crate::game::Pools;
+    use crate::player;
+    use crate::prior::Prior;
+    use crate::wordlist;
+    use crate::wordlist::Duplicates;
+    use std::collections::HashMap;
+
+    // 'cañas' is written with a combining tilde on purpose
+    const SPANISH: &str = "#alphabet abcdefghijklmnñopqrstuvwxyz áéíóúü
+# a few spanish words
+ñandú
+cañón
+niños
+sueño
+can\u{0303}as
+perro
+árbol
+pingüino
+";
+
+    fn spanish_list() -> wordlist::WordList {
+        return wordlist::parse("spanish", SPANISH, Duplicates::Drop, &Alphabet::english());
+    }
+
+    #[test]
+    fn letters_are_graphemes() {
+        assert_eq!(letters("n\u{0303}a"), vec!["n\u{0303}", "a"]);
+        assert_eq!(letter_count("ñandú"), 5);
+        assert_eq!(letter_count("crane"), 5);
+        assert_eq!(normalize("CAN\u{0303}AS"), "cañas");
+    }
+
+    #[test]
+    fn alphabet_directive_is_used() {
+        let list = spanish_list();
+        assert_eq!(list.words, vec!["ñandú", "cañón", "niños", "sueño", "cañas", "perro", "árbol"]);
+        assert!(list.alphabet.contains("ñ"));
+        // pingüino is too long, and that's the only problem
+        assert_eq!(list.problems.len(), 1);
+        assert_eq!(list.problems[0].text, "pingüino");
+    }
+
+    #[test]
+    fn english_rejects_other_letters() {
+        let list = wordlist::parse("english", "cañón\ncrane\n", Duplicates::Drop, &Alphabet::english());
+        assert_eq!(list.words, vec!["crane"]);
+        assert_eq!(list.problems[0].reason, "has letters that aren't in the alphabet: ñ ó");
+    }
+
+    #[test]
+    fn answers_use_the_guess_alphabet() {
+        let guesses = spanish_list();
+        let answers = wordlist::parse("answers", "sueño\n", Duplicates::Keep, &guesses.alphabet);
+        assert_eq!(answers.words, vec!["sueño"]);
+        assert!(answers.problems.is_empty());
+    }
+
+    #[test]
+    fn scoring_counts_letters_not_bytes() {
+        // n and ñ are different letters, the accent on the ó makes it a different letter too
+        assert_eq!(game::determine_board_results("cañón", "cañas"), vec![2, 2, 2, 0, 0]);
+        assert_eq!(game::determine_board_results("niños", "cañón"), vec![0, 0, 2, 0, 1]);
+        assert_eq!(game::determine_board_results("sueño", "sueño"), vec![2, 2, 2, 2, 2]);
+        let list = spanish_list();
+        for answer in list.words.iter() {
+            for guess_word in list.words.iter() {
+                let state_vec = game::determine_board_results(answer, guess_word);
+                assert_eq!(game::pattern_code(answer, guess_word), game::encode_pattern(&state_vec));
+            }
+        }
+    }
+
+    #[test]
+    fn solver_plays_spanish() {
+        let list = spanish_list();
+        let words: Vec<&str> = list.words.iter().map(|word| word.as_str()).collect();
+        let pools = Pools::new(&words, &words, Prior::uniform(), game::Feedback::Colors);
+        let mut board_state: HashMap<String,Vec<u8>> = HashMap::new();
+        let mut candidates = pools.possible.clone();
+        for turn in 0..game::MAX_GUESSES {
+            let guess_word = player::best_guess(player::Strategy::Entropy, &pools, &candidates, &board_state, &[], turn);
+            assert!(words.contains(&guess_word.as_str()));
+            let state_vec = game::determine_board_results("sueño", &guess_word);
+            if game::is_solved(&state_vec) {
+                return;
+            }
+            candidates = game::filter_candidates(&candidates, &guess_word, &state_vec);
+            board_state.insert(guess_word, state_vec);
+        }
+        panic!("sueño was not solved");
+    }
+}
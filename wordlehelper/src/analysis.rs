@@ -0,0 +1,133 @@
+/*
+* post game analysis. replays a finished game and grades every guess, like wordlebot.
+* skill compares the expected information of the guess to the best guess the strategy could find,
+* luck is how the result compared to every other result the guess could have gotten.
+*/
+
+use std::collections::HashMap;
+
+use serde_json::json;
+
+use crate::alphabet;
+use crate::game;
+use crate::game::Pools;
+use crate::output;
+use crate::player;
+use crate::player::Strategy;
+use crate::render;
+
+pub struct Grade {
+    pub guess_word: String,
+    pub state_vec: Vec<u8>,
+    // candidates that fit the board before and after this guess
+    pub remaining_before: usize,
+    pub remaining_after: usize,
+    // what the strategy would have played instead
+    pub best_word: String,
+    // expected information in bits for the guess and for the strategy's pick
+    pub guess_bits: f64,
+    pub best_bits: f64,
+    // both scored out of 99
+    pub skill: u32,
+    pub luck: u32,
+}
+
+// grade every guess in the order it was played
+pub fn grade_game(strategy: Strategy, pools: &Pools, past_answers: &[&str], history: &[(String,Vec<u8>)]) -> Vec<Grade> {
+    let mut grades: Vec<Grade> = Vec::new();
+    let mut board_state: HashMap<String,Vec<u8>> = HashMap::new();
+    let mut candidates: Vec<&str> = pools.possible.clone();
+
+    for (turn, (guess_word, state_vec)) in history.iter().enumerate() {
+        let best_word = player::best_guess(strategy, pools, &candidates, &board_state, past_answers, turn);
+        let remaining: Vec<&str> = game::filter_candidates(&candidates, guess_word, state_vec);
+
+        let guess_bits = player::expected_information(pools, &candidates, guess_word);
+        let best_bits = player::expected_information(pools, &candidates, &best_word);
+        grades.push(Grade {
+            guess_word: guess_word.clone(),
+            state_vec: state_vec.clone(),
+            remaining_before: candidates.len(),
+            remaining_after: remaining.len(),
+            best_word,
+            guess_bits,
+            best_bits,
+            skill: skill(&candidates, guess_word, guess_bits, best_bits),
+            luck: luck(pools, &candidates, guess_word, state_vec),
+        });
+
+        board_state.insert(guess_word.clone(), state_vec.clone());
+        candidates = remaining;
+    }
+    return grades;
+}
+
+// the guess's information as a share of the best guess's. beating the strategy's pick still caps out at 99.
+fn skill(candidates: &[&str], guess_word: &str, guess_bits: f64, best_bits: f64) -> u32 {
+    // with one word left there's nothing to learn, the only good guess is that word
+    if candidates.len() <= 1 {
+        return if candidates.contains(&guess_word) { 99 } else { 0 };
+    }
+    let best_bits = best_bits.max(guess_bits);
+    if best_bits <= 0.0 {
+        return 0;
+    }
+    return (99.0 * guess_bits / best_bits).round() as u32;
+}
+
+// the chance that the result would have left more words than the result we actually got.
+// ties count half, so an average result scores around 50.
+fn luck(pools: &Pools, candidates: &[&str], guess_word: &str, state_vec: &[u8]) -> u32 {
+    if candidates.is_empty() {
+        return 0;
+    }
+    let buckets = game::bucket_sizes(pools.feedback, candidates, guess_word);
+    let chances = player::pattern_chances(pools, candidates, guess_word);
+    let actual = buckets[pools.feedback.encode(state_vec, alphabet::letter_count(guess_word)) as usize];
+    let mut better = 0.0;
+    for (size, chance) in buckets.iter().zip(chances.iter()) {
+        if *size > actual {
+            better += chance;
+        }
+        else if *size == actual {
+            better += chance / 2.0;
+        }
+    }
+    return (99.0 * better).round() as u32;
+}
+
+pub fn print_report(grades: &[Grade]) {
+    if !output::is_text() {
+        for (turn, grade) in grades.iter().enumerate() {
+            let pattern: String = grade.state_vec.iter().map(|state| state.to_string()).collect();
+            output::record("grade", json!({
+                "turn": turn + 1,
+                "guess": grade.guess_word,
+                "pattern": pattern,
+                "remaining": grade.remaining_after,
+                "score": (grade.guess_bits * 1000.0).round() / 1000.0,
+                "best": grade.best_word,
+                "best_score": (grade.best_bits * 1000.0).round() / 1000.0,
+                "skill": grade.skill,
+                "luck": grade.luck,
+            }));
+        }
+        return;
+    }
+    println!("turn  guess                left  eliminated  bits  best    bits  skill  luck");
+    for (turn, grade) in grades.iter().enumerate() {
+        println!("{:>4}  {}  {:>5}  {:>10}  {:>4.2}  {:<6}  {:>4.2}  {:>5}  {:>4}",
+            turn + 1,
+            render::tiles(&grade.guess_word, &grade.state_vec),
+            grade.remaining_before,
+            grade.remaining_before - grade.remaining_after,
+            grade.guess_bits,
+            grade.best_word,
+            grade.best_bits,
+            grade.skill,
+            grade.luck);
+    }
+    if let Some(turn) = grades.iter().position(|grade| grade.remaining_after == 0) {
+        output::say(&format!("no word in the list fits the results from turn {}, check them for typos.", turn + 1));
+    }
+}
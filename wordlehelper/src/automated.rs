@@ -0,0 +1,93 @@
+/*
+* automated mode. the solver plays a puzzle against the answer it already knows, printing each game
+* as it goes. the benchmark is this for every day in the answer list.
+*/
+
+use std::collections::HashMap;
+
+use serde_json::json;
+
+use crate::alphabet;
+use crate::game;
+use crate::game::Pools;
+use crate::output;
+use crate::player;
+use crate::player::Strategy;
+
+// receive words, answers, and day. pick answer word. begin loop of calling player, validating guess, and returning guess results
+// the first guess never depends on the day, so it's worked out once and kept in opener for the next game.
+// returns every guess and its results so the board can be drawn. quiet plays without printing anything, for stats.
+pub fn play(pools: &Pools, answers: &[&str], day: usize, strategy: Strategy, opener: &mut Option<String>, quiet: bool) -> Vec<(String,Vec<u8>)> {
+    // grab a word to be the answer
+    let answer = answers[day];
+
+    // trim the answers to only have old answers. not current or future ones
+    let mut answers = answers.to_vec();
+    answers.resize(day,"");
+
+    // board state tracks all guesses and the results of those guesses.
+    // value is a hot encoding where 0 is a miss, 1 is an incorrect position, 2's are correct positions.
+    let mut board_state: HashMap<String,Vec<u8>> = HashMap::new();
+
+    // loop counter keeps track of how many guesses it took
+    let mut loop_counter = 0;
+    // guesses keeps track of all guesses, to be printed to the user
+    let mut guesses: Vec<String> = Vec::new();
+    // history keeps the guesses and their results in the order they were played
+    let mut history: Vec<(String,Vec<u8>)> = Vec::new();
+    // candidates are the possible answers that still fit the board
+    let mut candidates: Vec<&str> = pools.possible.clone();
+    // loop and check answer
+    loop {
+
+        // get a word from the strategy, reusing the opener if we have one
+        let guess_word = match (loop_counter, opener.as_ref()) {
+            (0, Some(opener)) => opener.clone(),
+            _ => player::best_guess(strategy, pools, &candidates, &board_state, &answers, loop_counter),
+        };
+        if loop_counter == 0 {
+            *opener = Some(guess_word.clone());
+        }
+        guesses.push(guess_word.clone());
+        if guess_word.is_empty(){
+            if !quiet && output::is_text() {
+                println!("failed to guess word {:?}",guesses);
+            }
+            break
+        }
+
+        // get board results
+        let state_vec = pools.feedback.results(answer, &guess_word);
+        history.push((guess_word.clone(),state_vec.clone()));
+
+        // update loop counter to match guess count
+        loop_counter += 1;
+
+        // the board state only needs updating if the game goes on, but the record wants the candidates left either way
+        let remaining = game::filter_by_feedback(pools.feedback, &candidates, &guess_word, &state_vec);
+        if !quiet {
+            output::turn(pools, json!({ "puzzle": day }), loop_counter, &candidates, &guess_word, &state_vec, remaining.len());
+        }
+
+        // quit if we're successful
+        if pools.feedback.is_solved(&state_vec, alphabet::letter_count(&guess_word)) {
+            if !quiet && output::is_text() {
+                println!("day {} : guessed '{}' in {} guesses. {:?}",day,guess_word,loop_counter,guesses);
+            }
+            // statistics print statement.
+//            println!("{},{}",day,loop_counter);
+            break
+        }
+
+        // update the board state
+        candidates = remaining;
+        board_state.insert(guess_word.clone(),state_vec);
+    }
+
+    if !quiet {
+        let solved = history.last().is_some_and(|(guess_word, _)| guess_word == answer);
+        let words: Vec<&str> = history.iter().map(|(guess_word, _)| guess_word.as_str()).collect();
+        output::record("game", json!({ "puzzle": day, "answer": answer, "solved": solved, "guesses": history.len(), "words": words }));
+    }
+    return history;
+}
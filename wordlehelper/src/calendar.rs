@@ -0,0 +1,65 @@
+/*
+* maps calendar dates to wordle day numbers and back.
+* wordle #0 was played on 2021-06-19, every day after that is one more puzzle.
+*/
+
+use chrono::{Local, NaiveDate};
+
+// the date of wordle #0
+pub fn day_zero() -> NaiveDate {
+    return NaiveDate::from_ymd_opt(2021, 6, 19).expect("day zero is a valid date");
+}
+
+// today's date in the local timezone
+pub fn today() -> NaiveDate {
+    return Local::now().date_naive();
+}
+
+// parse a date like "2022-03-03"
+pub fn parse_date(date: &str) -> Result<NaiveDate, String> {
+    return NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .map_err(|_| format!("Could not parse date '{}'. Use the format YYYY-MM-DD", date));
+}
+
+// get the puzzle number for a date. dates before wordle existed are an error.
+pub fn day_from_date(date: NaiveDate) -> Result<usize, String> {
+    let days = (date - day_zero()).num_days();
+    if days < 0 {
+        return Err(format!("{} is before the first wordle ({})", date, day_zero()));
+    }
+    return Ok(days as usize);
+}
+
+// get the date a puzzle number was played on. None for a day too far out for a date to represent
+pub fn date_from_day(day: usize) -> Option<NaiveDate> {
+    return day_zero().checked_add_days(chrono::Days::new(day as u64));
+}
+
+// a puzzle number with its date, like "puzzle 257 (2022-03-03)". the date is left off if there isn't one
+pub fn describe_day(day: usize) -> String {
+    return match date_from_day(day) {
+        Some(date) => format!("puzzle {} ({})", day, date),
+        None => format!("puzzle {}", day),
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn days_and_dates_round_trip() {
+        let date = NaiveDate::from_ymd_opt(2022, 3, 3).unwrap();
+        assert_eq!(day_from_date(date), Ok(257));
+        assert_eq!(date_from_day(257), Some(date));
+        assert_eq!(describe_day(257), "puzzle 257 (2022-03-03)");
+        assert!(day_from_date(NaiveDate::from_ymd_opt(2021, 6, 18).unwrap()).is_err());
+    }
+
+    #[test]
+    fn days_past_the_last_date_have_no_date() {
+        assert_eq!(date_from_day(usize::MAX), None);
+        assert_eq!(date_from_day(99999999), None);
+        assert_eq!(describe_day(99999999), "puzzle 99999999");
+    }
+}
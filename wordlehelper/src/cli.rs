@@ -0,0 +1,356 @@
+/*
+* command line parsing. turns the raw args into options for main.
+*/
+
+use chrono::NaiveDate;
+
+use crate::calendar;
+use crate::game::Feedback;
+use crate::output::Format;
+use crate::player::Strategy;
+use crate::render::ColorChoice;
+
+pub const USAGE: &str = "run like $ ./wordlehelper i [--date 2022-03-03 | --puzzle 257] [--words wordle_words.txt] [--answers ny_times_answers.txt]
+the word lists default to the ones built into the binary. giving the two files first still works too, like
+  $ ./wordlehelper ../../words/wordle_words.txt ../../words/ny_times_answers.txt i
+modes:
+  a          automated, the solver plays the day by itself. use --all to play every day in the answer list
+  i, play    interactive, the solver suggests guesses and you enter the results like '00120'.
+             the answer is never shown unless --check is given, which reveals it after the game and checks your results.
+             --tui plays on a full screen board where you pick tile colors instead of typing them, in builds with
+             the tui feature like $ cargo run --features tui -- i --tui
+             the game is saved after every guess, --resume carries on with the last unfinished one
+  w WORD...  was WORD the answer to a puzzle before the day being played?
+  p          practice, guess a random answer yourself and see what the solver would have played. --seed N repeats a game
+  r          review, enter a game you played like 'crane 00120' per line and get each guess graded
+  s          share import, paste a shared emoji grid and see which words could have made each row
+  d          deduce, paste several people's grids for the same day and rank the answers that could have made them
+  x          absurdle, guess against a host that never picks an answer and dodges your guesses, then watch the solver.
+             'a --absurdle' has the solver play the host by itself. both use the minimax strategy unless told otherwise
+  stats      your win rate, streaks and guesses from the finished games in --state-dir, next to the solver's
+             guesses on the same puzzles with --strategy. --game and --feedback pick which games count
+  engine     a line protocol on stdin and stdout for bots and guis. send commands like 'guess crane 00120',
+             'go', 'go top 5', 'candidates', 'newgame' and 'quit'. every reply ends with 'ok' or an 'error' line
+  serve      a local http api on --port (8080) with POST /suggest, GET /candidates and POST /score.
+             only in builds with the server feature, like $ cargo run --features server -- serve
+options:
+  --game wordle|nerdle                    nerdle plays 8 symbol equations like '12+35=47' instead of words. the
+                                          equations are generated, puzzle N is the Nth one and practice picks any
+  --guesses FILE                          allowed guesses, one per line. --words works too. for other languages
+                                          add a line like '#alphabet abcdefghijklmnñopqrstuvwxyz' with every letter
+  --answers FILE                          possible answers in puzzle order, one per line. the solver only
+                                          counts letters and candidates from these, but guesses from both
+  --unknown-answers                       play as if the answer list isn't known, any allowed word might be the answer
+  --prior FILE                            'word,count' lines saying how common each word is. the entropy and expected
+                                          strategies weight candidates by it, common words count as more likely answers
+  --strategy frequency|entropy|expected|minimax
+                                          how the solver picks guesses, defaults to frequency
+  --feedback colors|jotto|mastermind      what the game says about a guess in modes a, i and engine. jotto only says how many
+                                          letters are in common, enter it like '3' or 'win'. mastermind gives black
+                                          and white pegs, enter them like '2 1'. both default to the entropy strategy
+  --color auto|always|never               color the board, auto only colors when printing to a terminal
+  --format text|json|jsonl|csv            json, jsonl and csv print records for scripts instead of text, a 'turn'
+                                          per guess with its pattern, the candidates left and its score in bits,
+                                          and a 'game' summary per game. prompts and warnings go to stderr
+  --top N                                 how many answers deduce lists, defaults to 20
+  --port N                                the port serve listens on, defaults to 8080
+  --state-dir DIR                         where saved games and the history of finished games are kept,
+                                          defaults to $XDG_STATE_HOME/wordlehelper or ~/.local/state/wordlehelper
+  --boards N                              play N boards at once like dordle (2), quordle (4) or octordle (8) in
+                                          modes a and i. automated plays --games random answer sets, defaults to 100.
+                                          guesses add up the entropy of every board, so it doesn't take --strategy
+  --seed N                                the seed for practice and the multi board benchmark. without it one is
+                                          picked from the clock and printed, so a run can always be repeated
+the day defaults to today's date";
+
+// the puzzle number used by older scripts to mean "play every day"
+const LEGACY_ALL_DAYS: usize = 10000;
+
+const MODES: [&str; 12] = ["a", "i", "play", "w", "p", "r", "s", "d", "x", "stats", "engine", "serve"];
+
+pub enum Mode {
+    Automated,
+    Interactive,
+    PastAnswer(Vec<String>),
+    Practice,
+    Review,
+    ShareImport,
+    Deduce,
+    Absurdle,
+    Stats,
+    Engine,
+    Serve,
+}
+
+// which puzzle is being played
+#[derive(Clone, Copy)]
+pub enum Game {
+    Wordle,
+    // equations instead of words, the dictionary is generated instead of read from files
+    Nerdle,
+}
+
+impl Game {
+    pub fn parse(name: &str) -> Result<Game, String> {
+        return match name {
+            "wordle" => Ok(Game::Wordle),
+            "nerdle" => Ok(Game::Nerdle),
+            _ => Err(format!("Unknown game '{}'. Use wordle or nerdle", name)),
+        };
+    }
+
+    // what parse reads back
+    pub fn name(&self) -> &'static str {
+        return match self {
+            Game::Wordle => "wordle",
+            Game::Nerdle => "nerdle",
+        };
+    }
+
+    // how the game's share grid starts
+    pub fn share_title(&self) -> &'static str {
+        return match self {
+            Game::Wordle => "Wordle",
+            Game::Nerdle => "nerdlegame",
+        };
+    }
+}
+
+// which day to play. resolved against the answer list once it's loaded.
+pub enum DaySpec {
+    Today,
+    Date(NaiveDate),
+    Puzzle(usize),
+    All,
+}
+
+pub struct Options {
+    pub game: Game,
+    // None means use the built in lists
+    pub word_file: Option<String>,
+    pub answer_file: Option<String>,
+    // word frequencies to weight candidates by
+    pub prior_file: Option<String>,
+    // candidates come from every allowed word instead of the answer list
+    pub unknown_answers: bool,
+    // where interactive games are saved, None means the default place
+    pub state_dir: Option<String>,
+    // carry on with the saved interactive game
+    pub resume: bool,
+    pub mode: Mode,
+    pub day: DaySpec,
+    // reveal the answer after an interactive game and check the entered results against it
+    pub check: bool,
+    // interactive mode on the full screen board
+    pub tui: bool,
+    // the solver plays the absurdle host instead of a day
+    pub absurdle: bool,
+    // seed for anything random, picked from the clock when not given
+    pub seed: Option<u64>,
+    pub strategy: Strategy,
+    pub feedback: Feedback,
+    pub color: ColorChoice,
+    pub format: Format,
+    pub top: usize,
+    // where serve listens
+    pub port: u16,
+    // more than one means a multi board game
+    pub boards: usize,
+    // how many random answer sets the multi board benchmark plays
+    pub games: usize,
+}
+
+impl DaySpec {
+    // turn the day into an index into the answer list, complaining if there's no answer for it
+    pub fn resolve(&self, answer_count: usize) -> Result<usize, String> {
+        let day = match self {
+            DaySpec::Today => calendar::day_from_date(calendar::today())?,
+            DaySpec::Date(date) => calendar::day_from_date(*date)?,
+            DaySpec::Puzzle(day) => *day,
+            DaySpec::All => return Err("--all can only be used in automated mode".to_string()),
+        };
+        if answer_count == 0 {
+            return Err(format!("There is no answer for {}. The answer list is empty", calendar::describe_day(day)));
+        }
+        if day >= answer_count {
+            return Err(format!(
+                "There is no answer for {}. The answer list ends at {}",
+                calendar::describe_day(day),
+                calendar::describe_day(answer_count.saturating_sub(1))
+            ));
+        }
+        return Ok(day);
+    }
+}
+
+pub fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut positional: Vec<&str> = Vec::new();
+    let mut game = Game::Wordle;
+    let mut word_file = None;
+    let mut answer_file = None;
+    let mut prior_file = None;
+    let mut unknown_answers = false;
+    let mut state_dir = None;
+    let mut resume = false;
+    let mut day = DaySpec::Today;
+    let mut check = false;
+    let mut tui = false;
+    let mut seed = None;
+    // None until --strategy is given, the default depends on the mode
+    let mut strategy = None;
+    let mut feedback = Feedback::Colors;
+    let mut absurdle = false;
+    let mut color = ColorChoice::Auto;
+    let mut format = Format::Text;
+    let mut top = 20;
+    let mut port = 8080;
+    let mut boards = 1;
+    let mut games = 100;
+
+    // skip the program name
+    let mut i = 1;
+    while i < args.len() {
+        let arg = args[i].as_str();
+        match arg {
+            "--game" => game = Game::parse(next_value(args, &mut i)?)?,
+            "--words" | "--guesses" => word_file = Some(next_value(args, &mut i)?.to_string()),
+            "--answers" => answer_file = Some(next_value(args, &mut i)?.to_string()),
+            "--prior" => prior_file = Some(next_value(args, &mut i)?.to_string()),
+            "--unknown-answers" => unknown_answers = true,
+            "--state-dir" => state_dir = Some(next_value(args, &mut i)?.to_string()),
+            "--resume" => resume = true,
+            "--date" => day = DaySpec::Date(calendar::parse_date(next_value(args, &mut i)?)?),
+            "--puzzle" | "--day" => day = parse_puzzle(next_value(args, &mut i)?)?,
+            "--all" => day = DaySpec::All,
+            "--check" => check = true,
+            "--tui" => tui = true,
+            "--strategy" => strategy = Some(Strategy::parse(next_value(args, &mut i)?)?),
+            "--feedback" => feedback = Feedback::parse(next_value(args, &mut i)?)?,
+            "--absurdle" => absurdle = true,
+            "--color" => color = ColorChoice::parse(next_value(args, &mut i)?)?,
+            "--format" => format = Format::parse(next_value(args, &mut i)?)?,
+            "--top" => top = next_value(args, &mut i)?.parse::<usize>().map_err(|_| "Could not parse --top".to_string())?,
+            "--boards" => boards = parse_count("--boards", next_value(args, &mut i)?)?,
+            "--games" => games = parse_count("--games", next_value(args, &mut i)?)?,
+            "--port" => port = next_value(args, &mut i)?.parse::<u16>().map_err(|_| "Could not parse --port".to_string())?,
+            "--seed" => seed = Some(parse_seed(next_value(args, &mut i)?)?),
+            _ if arg.starts_with("--") => return Err(format!("Unknown option '{}'", arg)),
+            _ => positional.push(arg),
+        }
+        i += 1;
+    }
+
+    // older scripts pass the word files first, like `words answers i`
+    let mut mode_args = &positional[..];
+    if !positional.is_empty() && !MODES.contains(&positional[0]) {
+        if positional.len() < 3 {
+            return Err(format!("Not enough args, {}", USAGE));
+        }
+        word_file = Some(positional[0].to_string());
+        answer_file = Some(positional[1].to_string());
+        mode_args = &positional[2..];
+    }
+    if mode_args.is_empty() {
+        return Err(format!("No game mode given, {}", USAGE));
+    }
+    // and the day as a positional before the mode, like `words answers 257 i`
+    if mode_args.len() == 2 && mode_args[0].parse::<usize>().is_ok() {
+        day = parse_puzzle(mode_args[0])?;
+        mode_args = &mode_args[1..];
+    }
+    let mode = match (mode_args[0], mode_args.len()) {
+        ("a", 1) => Mode::Automated,
+        ("i", 1) | ("play", 1) => Mode::Interactive,
+        ("p", 1) => Mode::Practice,
+        ("r", 1) => Mode::Review,
+        ("s", 1) => Mode::ShareImport,
+        ("d", 1) => Mode::Deduce,
+        ("x", 1) => Mode::Absurdle,
+        ("stats", 1) => Mode::Stats,
+        ("engine", 1) => Mode::Engine,
+        ("serve", 1) => Mode::Serve,
+        ("w", 1) => return Err("'w' needs at least one word to look up".to_string()),
+        ("w", _) => Mode::PastAnswer(mode_args[1..].iter().map(|word| word.to_string()).collect()),
+        ("a", _) | ("i", _) | ("play", _) | ("p", _) | ("r", _) | ("s", _) | ("d", _) | ("x", _) | ("stats", _) | ("engine", _) | ("serve", _) => return Err(format!("Too many args, {}", USAGE)),
+        _ => return Err("Invalid game mode. Please use 'a', 'i', 'play', 'w', 'p', 'r', 's', 'd', 'x', 'stats', 'engine' or 'serve'.".to_string()),
+    };
+    // the multi board solver only knows how to add up entropy across the boards
+    if boards > 1 && strategy.is_some() {
+        return Err("--strategy can't be used with --boards, multi board games always add up the entropy of every board".to_string());
+    }
+    // the absurdle host always picks the worst case, so that's what the solver plays against there
+    let absurdle_game = absurdle || matches!(mode, Mode::Absurdle);
+    let strategy = match (strategy, feedback) {
+        (Some(strategy), _) => strategy,
+        (None, Feedback::Colors) if absurdle_game => Strategy::Minimax,
+        (None, Feedback::Colors) => Strategy::Frequency,
+        (None, _) => Strategy::Entropy,
+    };
+    strategy.check_feedback(feedback)?;
+    if resume && !matches!(mode, Mode::Interactive) {
+        return Err("--resume only works with mode 'i'".to_string());
+    }
+    if tui && (feedback != Feedback::Colors || boards > 1 || !matches!(mode, Mode::Interactive)) {
+        return Err("--tui only works with mode 'i' on a single board with wordle colors".to_string());
+    }
+    // the engine talks its own line protocol on stdout, records would get mixed into the replies
+    if format != Format::Text && matches!(mode, Mode::Engine) {
+        return Err("--format doesn't work with mode 'engine', it always answers in its own protocol".to_string());
+    }
+    if feedback != Feedback::Colors && (absurdle_game || boards > 1 || !matches!(mode, Mode::Automated | Mode::Interactive | Mode::Stats | Mode::Engine)) {
+        return Err("--feedback only works with modes 'a', 'i', 'stats' and 'engine' on a single board".to_string());
+    }
+
+    return Ok(Options {
+        game,
+        word_file,
+        answer_file,
+        prior_file,
+        unknown_answers,
+        state_dir,
+        resume,
+        mode,
+        day,
+        check,
+        tui,
+        absurdle,
+        seed,
+        strategy,
+        feedback,
+        color,
+        format,
+        top,
+        port,
+        boards,
+        games,
+    });
+}
+
+// grab the value that follows a flag
+fn next_value<'a>(args: &'a [String], i: &mut usize) -> Result<&'a str, String> {
+    let flag = &args[*i];
+    *i += 1;
+    return match args.get(*i) {
+        Some(value) => Ok(value.as_str()),
+        None => Err(format!("{} needs a value", flag)),
+    };
+}
+
+fn parse_puzzle(value: &str) -> Result<DaySpec, String> {
+    return match value.parse::<usize>() {
+        Ok(LEGACY_ALL_DAYS) => Ok(DaySpec::All),
+        Ok(day) => Ok(DaySpec::Puzzle(day)),
+        Err(_) => Err(format!("Could not parse puzzle number '{}'", value)),
+    };
+}
+
+fn parse_count(flag: &str, value: &str) -> Result<usize, String> {
+    return match value.parse::<usize>() {
+        Ok(count) if count > 0 => Ok(count),
+        _ => Err(format!("{} needs a number above 0, not '{}'", flag, value)),
+    };
+}
+
+fn parse_seed(value: &str) -> Result<u64, String> {
+    return value.parse::<u64>().map_err(|_| format!("Could not parse seed '{}'", value));
+}
@@ -0,0 +1,181 @@
+/*
+* engine mode, a line protocol on stdin and stdout for bots and guis, a bit like uci for chess engines.
+* the engine holds the game that interactive mode would, so nothing has to scrape prompts.
+* one command per line. every reply ends with a line that's either 'ok' or 'error <what went wrong>'.
+*   newgame                 forget the guesses and start over
+*   guess crane 00120       play a guess and what the game said, replies 'remaining 12' and 'solved' once it is
+*   go                      the solver's guess, 'bestguess crane'
+*   go top 5                the best 5 guesses, one 'guess crane 5.885' line each with the strategy's score
+*   candidates              'candidates 3 tiger timer tiler'
+*   strategy entropy        change how go picks guesses
+*   quit                    stop, the end of the input works too
+* 'ready' is printed once the word lists are loaded.
+*/
+
+use std::collections::HashMap;
+use std::io::{BufRead, Write};
+
+use crate::alphabet;
+use crate::game;
+use crate::game::Pools;
+use crate::player;
+use crate::player::Strategy;
+
+// the game the engine is following
+struct Engine<'a> {
+    pools: &'a Pools<'a>,
+    strategy: Strategy,
+    candidates: Vec<&'a str>,
+    board_state: HashMap<String,Vec<u8>>,
+    // guesses played so far, the frequency strategy changes what it does after the first two
+    turn: usize,
+}
+
+impl<'a> Engine<'a> {
+    fn new(pools: &'a Pools<'a>, strategy: Strategy) -> Engine<'a> {
+        return Engine { pools, strategy, candidates: pools.possible.clone(), board_state: HashMap::new(), turn: 0 };
+    }
+
+    // answer one command. None means quit
+    fn command(&mut self, line: &str) -> Option<Result<Vec<String>, String>> {
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        let reply = match parts.as_slice() {
+            [] => Ok(Vec::new()),
+            ["quit"] => return None,
+            ["newgame"] => {
+                *self = Engine::new(self.pools, self.strategy);
+                Ok(Vec::new())
+            }
+            ["guess", guess_word, result @ ..] if !result.is_empty() => self.guess(guess_word, &result.join(" ")),
+            ["go"] => self.go(1).map(|guesses| vec![format!("bestguess {}", guesses[0].0)]),
+            ["go", "top", count] => match count.parse::<usize>() {
+                Ok(count) if count > 0 => self.go(count).map(|guesses| guesses.iter().map(|(guess_word, score)| format!("guess {} {:.3}", guess_word, score)).collect()),
+                _ => Err(format!("Could not read the count '{}'", count)),
+            },
+            ["candidates"] => Ok(vec![format!("candidates {} {}", self.candidates.len(), self.candidates.join(" "))]),
+            ["strategy", name] => Strategy::parse(name)
+                .and_then(|strategy| strategy.check_feedback(self.pools.feedback).map(|_| strategy))
+                .map(|strategy| {
+                    self.strategy = strategy;
+                    Vec::new()
+                }),
+            _ => Err(format!("Unknown command '{}'. Use newgame, guess, go, go top N, candidates, strategy or quit", line.trim())),
+        };
+        return Some(reply);
+    }
+
+    fn guess(&mut self, guess_word: &str, result: &str) -> Result<Vec<String>, String> {
+        let guess_word = alphabet::normalize(guess_word);
+        let word_len = alphabet::letter_count(&guess_word);
+        if let Some(expected) = self.pools.possible.first().map(|word| alphabet::letter_count(word)).filter(|expected| *expected != word_len) {
+            return Err(format!("'{}' isn't {} long", guess_word, expected));
+        }
+        let results = self.pools.feedback.parse_results(result, word_len)?;
+        self.candidates = game::filter_by_feedback(self.pools.feedback, &self.candidates, &guess_word, &results);
+        self.turn += 1;
+        let mut reply = vec![format!("remaining {}", self.candidates.len())];
+        if self.pools.feedback.is_solved(&results, word_len) {
+            reply.push("solved".to_string());
+        }
+        self.board_state.insert(guess_word, results);
+        return Ok(reply);
+    }
+
+    // the frequency strategy only has a best guess, the others can rank every word
+    fn go(&self, count: usize) -> Result<Vec<(String, f64)>, String> {
+        if self.candidates.is_empty() {
+            return Err("No words fit the guesses so far".to_string());
+        }
+        if count == 1 {
+            let guess_word = player::best_guess(self.strategy, self.pools, &self.candidates, &self.board_state, &[], self.turn);
+            let score = player::score(self.strategy, self.pools, &self.candidates, &guess_word);
+            return Ok(vec![(guess_word, score)]);
+        }
+        return Ok(player::top_guesses(self.strategy, self.pools, &self.candidates, count));
+    }
+}
+
+// follow commands until quit or the input runs out
+pub fn run<R: BufRead, W: Write>(pools: &Pools, strategy: Strategy, input: R, mut output: W) -> Result<(), String> {
+    let write_error = |error: std::io::Error| format!("Could not write a reply: {}", error);
+    let mut engine = Engine::new(pools, strategy);
+    writeln!(output, "ready").and_then(|_| output.flush()).map_err(write_error)?;
+    for line in input.lines() {
+        let line = line.map_err(|error| format!("Could not read a command: {}", error))?;
+        let reply = match engine.command(&line) {
+            Some(reply) => reply,
+            None => break,
+        };
+        match reply {
+            Ok(lines) => {
+                for reply_line in lines.iter() {
+                    writeln!(output, "{}", reply_line).map_err(write_error)?;
+                }
+                writeln!(output, "ok").map_err(write_error)?;
+            }
+            Err(error) => writeln!(output, "error {}", error).map_err(write_error)?,
+        }
+        // whoever is on the other end is waiting for the reply
+        output.flush().map_err(write_error)?;
+    }
+    return Ok(());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::Feedback;
+    use crate::prior::Prior;
+
+    const WORDS: [&str; 6] = ["crane", "tiger", "slate", "timer", "tiler", "ozone"];
+    const ANSWERS: [&str; 4] = ["tiger", "timer", "tiler", "ozone"];
+
+    fn session(commands: &str) -> String {
+        return session_with(Feedback::Colors, commands);
+    }
+
+    fn session_with(feedback: Feedback, commands: &str) -> String {
+        let pools = Pools::new(&WORDS, &ANSWERS, Prior::uniform(), feedback);
+        let mut output: Vec<u8> = Vec::new();
+        run(&pools, Strategy::Entropy, commands.as_bytes(), &mut output).unwrap();
+        return String::from_utf8(output).unwrap();
+    }
+
+    #[test]
+    fn plays_a_game() {
+        let replies = session("guess crane 01001\ncandidates\nguess tiger 22222\nnewgame\ncandidates\nquit\ncandidates\n");
+        assert_eq!(replies, "ready\nremaining 3\nok\ncandidates 3 tiger timer tiler\nok\nremaining 1\nsolved\nok\nok\ncandidates 4 tiger timer tiler ozone\nok\n");
+    }
+
+    #[test]
+    fn go_suggests_guesses() {
+        let replies = session("go\ngo top 3\n");
+        let lines: Vec<&str> = replies.lines().collect();
+        assert!(lines[1].starts_with("bestguess "));
+        assert_eq!(lines[2], "ok");
+        assert_eq!(lines[3..6].iter().filter(|line| line.starts_with("guess ")).count(), 3);
+        assert_eq!(lines[6], "ok");
+    }
+
+    #[test]
+    fn mistakes_are_errors() {
+        let replies = session("guess crane 012\nfly\ngo top none\n");
+        let lines: Vec<&str> = replies.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert!(lines[1..].iter().all(|line| line.starts_with("error ")));
+    }
+
+    #[test]
+    fn frequency_needs_colors() {
+        // crane against tiger
+        for (feedback, result) in [(Feedback::CommonLetters, "2"), (Feedback::Pegs, "0 2")] {
+            let replies = session_with(feedback, &format!("strategy frequency\nguess crane {}\ngo\n", result));
+            let lines: Vec<&str> = replies.lines().collect();
+            assert_eq!(lines[1], "error The frequency strategy needs wordle colors, use entropy, expected or minimax");
+            // go still uses entropy
+            assert!(lines[lines.len() - 2].starts_with("bestguess "));
+            assert_eq!(lines[lines.len() - 1], "ok");
+        }
+        assert_eq!(session("strategy frequency\n"), "ready\nok\n");
+    }
+}
@@ -2,29 +2,476 @@
 * validates and answer and returns a vector with the results
 */
 
-// return the results of the guess
-pub fn determine_board_results(answer: &String, guess_word: &String) -> Vec<u8> {
-    let answer_chars: Vec<char> = answer.chars().collect();
-    let guess_word_chars: Vec<char> = guess_word.chars().collect();
-    let mut state_vec = vec![0,0,0,0,0];
+use std::collections::{BTreeSet, HashSet};
+
+use crate::alphabet;
+use crate::packed::Packed;
+use crate::prior::Prior;
+
+// the real game only gives you six tries
+pub const MAX_GUESSES: usize = 6;
+// what pattern_code gives for words it can't score. no result encodes to it, so nothing matches it
+pub const NO_PATTERN: u16 = u16::MAX;
+
+// every guess and its results, in the order they were played
+pub type History = Vec<(String,Vec<u8>)>;
+
+// the two word lists a game is played with. any allowed word can be guessed, but only the
+// possible answers can be the answer, so candidates and letter frequencies come from those.
+// the prior says how likely each possible answer is.
+// allowed is kept sorted so everything that walks it sees the words in the same order every run.
+pub struct Pools<'a> {
+    pub allowed: BTreeSet<&'a str>,
+    pub possible: Vec<&'a str>,
+    pub prior: Prior,
+    // what the game tells you about a guess
+    pub feedback: Feedback,
+    // the same words packed for the frequency heuristic
+    pub packed: Packed<'a>,
+}
+
+impl<'a> Pools<'a> {
+    // every possible answer is also allowed as a guess, even if the guess list forgot it
+    pub fn new(allowed: &[&'a str], possible: &[&'a str], prior: Prior, feedback: Feedback) -> Pools<'a> {
+        let mut allowed: BTreeSet<&str> = allowed.iter().copied().collect();
+        allowed.extend(possible.iter().copied());
+        // the answer list repeats a word if it was the answer more than once, that's still one possible answer
+        let mut seen: HashSet<&str> = HashSet::new();
+        let possible: Vec<&str> = possible.iter().copied().filter(|word| seen.insert(word)).collect();
+        let sorted_allowed: Vec<&str> = allowed.iter().copied().collect();
+        let packed = Packed::new(&sorted_allowed, &possible);
+        return Pools { allowed, possible, prior, feedback, packed };
+    }
+}
+
+// what a game says about a guess. every kind is worked out from the wordle colors and packed into a
+// pattern code, so the scoring strategies can split candidates into buckets the same way for all of them.
+// the results vector holds a state per letter for colors, [letters in common, 1 if it's the answer] for jotto
+// and [black pegs, white pegs] for mastermind.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Feedback {
+    // a color per letter, like wordle
+    Colors,
+    // only how many letters the guess has in common with the answer, wherever they are. like jotto
+    CommonLetters,
+    // black pegs for right letter in the right place, white pegs for right letter in the wrong place. like mastermind
+    Pegs,
+}
+
+impl Feedback {
+    pub fn parse(name: &str) -> Result<Feedback, String> {
+        return match name {
+            "colors" | "wordle" => Ok(Feedback::Colors),
+            "jotto" => Ok(Feedback::CommonLetters),
+            "mastermind" | "pegs" => Ok(Feedback::Pegs),
+            _ => Err(format!("Unknown feedback '{}'. Use colors, jotto or mastermind", name)),
+        };
+    }
+
+    // what parse reads back
+    pub fn name(&self) -> &'static str {
+        return match self {
+            Feedback::Colors => "colors",
+            Feedback::CommonLetters => "jotto",
+            Feedback::Pegs => "mastermind",
+        };
+    }
+
+    // score a guess against the answer
+    pub fn results(&self, answer: &str, guess_word: &str) -> Vec<u8> {
+        let word_len = alphabet::letter_count(guess_word);
+        return self.decode(self.code(answer, guess_word), word_len);
+    }
+
+    // the pattern code for a guess against the answer
+    pub fn code(&self, answer: &str, guess_word: &str) -> u16 {
+        let colors = pattern_code(answer, guess_word);
+        if colors == NO_PATTERN || *self == Feedback::Colors {
+            return colors;
+        }
+        let word_len = alphabet::letter_count(guess_word);
+        let (greens, yellows) = color_counts(colors, word_len);
+        return match self {
+            Feedback::CommonLetters => (greens + yellows) * 2 + (answer == guess_word) as u16,
+            _ => greens * (word_len as u16 + 1) + yellows,
+        };
+    }
+
+    pub fn encode(&self, results: &[u8], word_len: usize) -> u16 {
+        return match self {
+            Feedback::Colors => encode_pattern(results),
+            Feedback::CommonLetters => results[0] as u16 * 2 + results[1] as u16,
+            Feedback::Pegs => results[0] as u16 * (word_len as u16 + 1) + results[1] as u16,
+        };
+    }
+
+    fn decode(&self, code: u16, word_len: usize) -> Vec<u8> {
+        return match self {
+            Feedback::Colors => decode_pattern(code, word_len),
+            Feedback::CommonLetters => vec![(code / 2) as u8, (code % 2) as u8],
+            Feedback::Pegs => vec![(code / (word_len as u16 + 1)) as u8, (code % (word_len as u16 + 1)) as u8],
+        };
+    }
+
+    // the results for guessing the answer
+    pub fn solved(&self, word_len: usize) -> Vec<u8> {
+        return match self {
+            Feedback::Colors => vec![2; word_len],
+            Feedback::CommonLetters => vec![word_len as u8, 1],
+            Feedback::Pegs => vec![word_len as u8, 0],
+        };
+    }
+
+    // how many different codes there are for a word length
+    pub fn code_count(&self, word_len: usize) -> usize {
+        return match self {
+            Feedback::Colors => pattern_count(word_len),
+            Feedback::CommonLetters => (word_len + 1) * 2,
+            Feedback::Pegs => (word_len + 1) * (word_len + 1),
+        };
+    }
+
+    pub fn is_solved(&self, results: &[u8], word_len: usize) -> bool {
+        return match self {
+            Feedback::Colors => is_solved(results),
+            Feedback::CommonLetters => results.get(1) == Some(&1),
+            Feedback::Pegs => results.first() == Some(&(word_len as u8)),
+        };
+    }
+
+    // read results typed in by the user. '00120' for colors, '3' or 'win' for jotto, '2 1' (black then white) for mastermind
+    pub fn parse_results(&self, text: &str, word_len: usize) -> Result<Vec<u8>, String> {
+        let text = text.trim();
+        let numbers: Result<Vec<u8>, _> = text.split_whitespace().map(|number| number.parse::<u8>()).collect();
+        let results = match (self, numbers) {
+            (Feedback::Colors, _) => parse_colors(text)?,
+            (Feedback::CommonLetters, _) if text == "win" => vec![word_len as u8, 1],
+            (Feedback::CommonLetters, Ok(numbers)) if numbers.len() == 1 => vec![numbers[0], 0],
+            (Feedback::Pegs, Ok(numbers)) if numbers.len() == 2 => numbers,
+            (Feedback::CommonLetters, _) => return Err(format!("Could not read '{}'. enter how many letters are in common like '3', or 'win'", text)),
+            (Feedback::Pegs, _) => return Err(format!("Could not read '{}'. enter the black and white pegs like '2 1'", text)),
+        };
+        let fits = match self {
+            Feedback::Colors => results.len() == word_len,
+            Feedback::CommonLetters => (results[0] as usize) <= word_len,
+            // each count is checked on its own too so a big one can't wrap the sum around
+            Feedback::Pegs => results.iter().all(|count| *count as usize <= word_len) && results[0] as usize + results[1] as usize <= word_len,
+        };
+        if !fits {
+            return Err(format!("'{}' doesn't fit a {} letter word", text, word_len));
+        }
+        return Ok(results);
+    }
+
+    // the results in words, for printing
+    pub fn describe(&self, results: &[u8]) -> String {
+        return match self {
+            Feedback::Colors => results.iter().map(|state| state.to_string()).collect(),
+            Feedback::CommonLetters if results[1] == 1 => "solved".to_string(),
+            Feedback::CommonLetters => format!("{} in common", results[0]),
+            Feedback::Pegs => format!("{} black {} white", results[0], results[1]),
+        };
+    }
+}
+
+// how many greens and yellows are in a color pattern code
+fn color_counts(code: u16, word_len: usize) -> (u16, u16) {
+    let mut greens = 0;
+    let mut yellows = 0;
+    let mut rest = code;
+    for _ in 0..word_len {
+        match rest % 3 {
+            2 => greens += 1,
+            1 => yellows += 1,
+            _ => {}
+        }
+        rest /= 3;
+    }
+    return (greens, yellows);
+}
+
+// parse results like '00120' without panicking on bad input
+pub fn parse_colors(text: &str) -> Result<Vec<u8>, String> {
+    let text = text.trim();
+    let mut state_vec: Vec<u8> = Vec::new();
+    for entry in text.chars(){
+        match entry.to_digit(10) {
+            Some(state) if state <= 2 => state_vec.push(state as u8),
+            _ => return Err(format!("Could not parse results '{}'. use 0, 1 and 2 like '00120'", text)),
+        }
+    }
+    return Ok(state_vec);
+}
+
+// return the results of the guess. positions are letters, so 'ñ' is one position however it's typed
+pub fn determine_board_results(answer: &str, guess_word: &str) -> Vec<u8> {
+    let answer_chars: Vec<&str> = alphabet::letters(answer);
+    let guess_word_chars: Vec<&str> = alphabet::letters(guess_word);
+    let mut state_vec = vec![0; guess_word_chars.len()];
 
     // for every entry in the guess word:
     //   check if its in the right position, set the vector position to 2 if it is
-    //   else check if its in the word at all, set the vecotr position to 1 if it is
-    //   else set the position to 0, because the letter is not in the word
+    //   otherwise remember the answer letter, it can still make some other guess letter a 1
+    let mut unmatched: Vec<&str> = Vec::new();
     for i in 0..guess_word_chars.len(){
         if guess_word_chars[i] == answer_chars[i]{
             state_vec[i] = 2;
         }
-        else if answer_chars.contains(&guess_word_chars[i]){
+        else {
+            unmatched.push(answer_chars[i]);
+        }
+    }
+    // then for every entry that isn't a 2:
+    //   check if its in the unmatched letters, set the vector position to 1 and use that letter up if it is
+    //   else leave the position at 0, because the letter is not in the word (or every copy is already accounted for)
+    for i in 0..guess_word_chars.len(){
+        if state_vec[i] == 2 {
+            continue
+        }
+        if let Some(pos) = unmatched.iter().position(|c| *c == guess_word_chars[i]){
             state_vec[i] = 1;
+            unmatched.remove(pos);
+        }
+    }
+
+//    println!("{:?} : {:?}", guess_word_chars, state_vec);
+    return state_vec;
+}
+
+// true when every position is in the right place
+pub fn is_solved(state_vec: &[u8]) -> bool {
+    return state_vec.iter().all(|state| *state == 2);
+}
+
+// same scoring as determine_board_results, packed into one number (base 3, first letter is the lowest digit).
+// works on bytes without allocating since the strategies score every word against every candidate.
+// words with letters outside ascii go the slow way through determine_board_results.
+// words of different lengths can't be scored against each other and get NO_PATTERN.
+pub fn pattern_code(answer: &str, guess_word: &str) -> u16 {
+    if !answer.is_ascii() || !guess_word.is_ascii() {
+        if alphabet::letter_count(answer) != alphabet::letter_count(guess_word) {
+            return NO_PATTERN;
+        }
+        return encode_pattern(&determine_board_results(answer, guess_word));
+    }
+    let answer = answer.as_bytes();
+    let guess_word = guess_word.as_bytes();
+    if answer.len() != guess_word.len() || guess_word.len() > 16 {
+        return NO_PATTERN;
+    }
+    let mut state_vec = [0u8; 16];
+    // answer letters that weren't matched in position, counted per byte value
+    let mut unmatched = [0u8; 256];
+    for i in 0..guess_word.len(){
+        if guess_word[i] == answer[i]{
+            state_vec[i] = 2;
         }
         else {
-            state_vec[i] = 0;
+            unmatched[answer[i] as usize] += 1;
+        }
+    }
+    for i in 0..guess_word.len(){
+        if state_vec[i] != 2 && unmatched[guess_word[i] as usize] > 0 {
+            state_vec[i] = 1;
+            unmatched[guess_word[i] as usize] -= 1;
         }
     }
 
-//    println!("{:?} : {:?}", guess_word_chars, state_vec);
+    let mut code: u16 = 0;
+    for i in (0..guess_word.len()).rev(){
+        code = code * 3 + state_vec[i] as u16;
+    }
+    return code;
+}
+
+// turn a hot encoded result into its pattern code
+pub fn encode_pattern(state_vec: &[u8]) -> u16 {
+    return state_vec.iter().rev().fold(0, |code, state| code * 3 + *state as u16);
+}
+
+// turn a pattern code back into a hot encoded result
+pub fn decode_pattern(code: u16, word_len: usize) -> Vec<u8> {
+    let mut state_vec: Vec<u8> = Vec::with_capacity(word_len);
+    let mut rest = code;
+    for _ in 0..word_len {
+        state_vec.push((rest % 3) as u8);
+        rest /= 3;
+    }
     return state_vec;
 }
 
+// number of different pattern codes for a word length
+pub fn pattern_count(word_len: usize) -> usize {
+    return 3usize.pow(word_len as u32);
+}
+
+// quick checks a candidate has to pass to give a guess these colors. most candidates fail one,
+// so they're ruled out without working out their whole pattern. only for ascii words
+struct ColorCheck {
+    guess_word: [u8; 16],
+    word_len: usize,
+    // a bit per green position
+    greens: u16,
+    // a bit per byte value, letters that have to be in the answer and letters that can't be
+    present: u128,
+    absent: u128,
+}
+
+impl ColorCheck {
+    fn new(guess_word: &str, state_vec: &[u8]) -> Option<ColorCheck> {
+        let bytes = guess_word.as_bytes();
+        if !guess_word.is_ascii() || bytes.len() > 16 || bytes.len() != state_vec.len() {
+            return None;
+        }
+        let mut check = ColorCheck { guess_word: [0; 16], word_len: bytes.len(), greens: 0, present: 0, absent: 0 };
+        check.guess_word[..bytes.len()].copy_from_slice(bytes);
+        for i in 0..bytes.len(){
+            if state_vec[i] == 2 {
+                check.greens |= 1 << i;
+            }
+            if state_vec[i] > 0 {
+                check.present |= 1 << bytes[i];
+            }
+        }
+        // a gray letter is only missing from the answer if no other copy of it was colored
+        for i in 0..bytes.len(){
+            if state_vec[i] == 0 && check.present & (1 << bytes[i]) == 0 {
+                check.absent |= 1 << bytes[i];
+            }
+        }
+        return Some(check);
+    }
+
+    // false only when the candidate can't give these colors
+    fn might_match(&self, candidate: &str) -> bool {
+        let bytes = candidate.as_bytes();
+        if bytes.len() != self.word_len {
+            return true;
+        }
+        let mut letters: u128 = 0;
+        let mut same: u16 = 0;
+        let mut every_byte = 0;
+        for (i, byte) in bytes.iter().enumerate(){
+            every_byte |= byte;
+            letters |= 1 << (byte & 127);
+            same |= ((*byte == self.guess_word[i]) as u16) << i;
+        }
+        // letters outside ascii are left to the full pattern.
+        // greens are exactly where the letters are the same
+        return every_byte >= 128 || (same == self.greens && letters & self.present == self.present && letters & self.absent == 0);
+    }
+}
+
+// the candidates that would have given this result for this guess
+pub fn filter_candidates<'a>(candidates: &[&'a str], guess_word: &str, state_vec: &[u8]) -> Vec<&'a str> {
+    return filter_by_feedback(Feedback::Colors, candidates, guess_word, state_vec);
+}
+
+// the candidates that would have given these results for this guess, for any kind of feedback
+pub fn filter_by_feedback<'a>(feedback: Feedback, candidates: &[&'a str], guess_word: &str, results: &[u8]) -> Vec<&'a str> {
+    let code = feedback.encode(results, alphabet::letter_count(guess_word));
+    let check = if feedback == Feedback::Colors { ColorCheck::new(guess_word, results) } else { None };
+    return candidates.iter()
+        .filter(|candidate| check.as_ref().is_none_or(|check| check.might_match(candidate)))
+        .filter(|candidate| feedback.code(candidate, guess_word) == code)
+        .copied()
+        .collect();
+}
+
+// how many candidates land on each pattern code for a guess
+pub fn bucket_sizes(feedback: Feedback, candidates: &[&str], guess_word: &str) -> Vec<usize> {
+    let mut buckets = vec![0; feedback.code_count(alphabet::letter_count(guess_word))];
+    for candidate in candidates.iter(){
+        buckets[feedback.code(candidate, guess_word) as usize] += 1;
+    }
+    return buckets;
+}
+
+// every guess scored against every answer, worked out once up front.
+// row per guess, column per answer.
+pub struct PatternMatrix<'a> {
+    pub guesses: Vec<&'a str>,
+    pub answers: Vec<&'a str>,
+    codes: Vec<u16>,
+}
+
+impl<'a> PatternMatrix<'a> {
+    pub fn new(guesses: &[&'a str], answers: &[&'a str]) -> PatternMatrix<'a> {
+        let mut codes: Vec<u16> = Vec::with_capacity(guesses.len() * answers.len());
+        for guess_word in guesses.iter(){
+            for answer in answers.iter(){
+                codes.push(pattern_code(answer, guess_word));
+            }
+        }
+        return PatternMatrix { guesses: guesses.to_vec(), answers: answers.to_vec(), codes };
+    }
+
+    pub fn get(&self, guess_index: usize, answer_index: usize) -> u16 {
+        return self.codes[guess_index * self.answers.len() + answer_index];
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn board_results() {
+        assert_eq!(determine_board_results("crane", "crane"), vec![2, 2, 2, 2, 2]);
+        assert_eq!(determine_board_results("crane", "tipsy"), vec![0, 0, 0, 0, 0]);
+        assert_eq!(determine_board_results("crane", "nacre"), vec![1, 1, 1, 1, 2]);
+    }
+
+    #[test]
+    fn board_results_count_repeated_letters() {
+        // one t in the answer, so only the first t in the guess is yellow
+        assert_eq!(determine_board_results("tiger", "otter"), vec![0, 1, 0, 2, 2]);
+        // the green e uses up the answer's only e
+        assert_eq!(determine_board_results("crane", "eerie"), vec![0, 0, 1, 0, 2]);
+        // two e's in the answer make the first two e's yellow and leave the third gray
+        assert_eq!(determine_board_results("speed", "eerie"), vec![1, 1, 0, 0, 0]);
+    }
+
+    #[test]
+    fn pattern_codes_match_board_results() {
+        let words = ["crane", "tiger", "otter", "eerie", "speed", "geese", "abbey"];
+        for answer in words.iter() {
+            for guess_word in words.iter() {
+                let state_vec = determine_board_results(answer, guess_word);
+                assert_eq!(pattern_code(answer, guess_word), encode_pattern(&state_vec));
+                assert_eq!(decode_pattern(encode_pattern(&state_vec), 5), state_vec);
+            }
+        }
+    }
+
+    #[test]
+    fn words_of_different_lengths_match_nothing() {
+        assert_eq!(pattern_code("crane", "cranes"), NO_PATTERN);
+        assert_eq!(pattern_code("cranes", "crane"), NO_PATTERN);
+        assert_eq!(pattern_code("niño", "niños"), NO_PATTERN);
+        assert_eq!(Feedback::Pegs.code("crane", "cranes"), NO_PATTERN);
+        assert!(filter_candidates(&["crane", "tiger"], "cranes", &[0; 6]).is_empty());
+        assert!(filter_by_feedback(Feedback::CommonLetters, &["crane", "tiger"], "cranes", &[0, 0]).is_empty());
+    }
+
+    #[test]
+    fn peg_counts_have_to_fit_the_word() {
+        assert_eq!(Feedback::Pegs.parse_results("2 1", 5), Ok(vec![2, 1]));
+        assert!(Feedback::Pegs.parse_results("3 3", 5).is_err());
+        assert!(Feedback::Pegs.parse_results("200 100", 5).is_err());
+        assert!(Feedback::Pegs.parse_results("255 1", 5).is_err());
+        assert!(Feedback::CommonLetters.parse_results("6", 5).is_err());
+    }
+
+    #[test]
+    fn filtering_keeps_exactly_the_matching_candidates() {
+        let words = ["crane", "tiger", "otter", "eerie", "speed", "geese", "abbey", "there", "three", "ether"];
+        for answer in words.iter() {
+            for guess_word in words.iter() {
+                let state_vec = determine_board_results(answer, guess_word);
+                let expected: Vec<&str> = words.iter().copied().filter(|word| determine_board_results(word, guess_word) == state_vec).collect();
+                assert_eq!(filter_candidates(&words, guess_word, &state_vec), expected);
+            }
+        }
+    }
+}
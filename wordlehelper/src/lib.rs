@@ -0,0 +1,34 @@
+/*
+* the solver and everything the modes are built from. main.rs reads the args and picks a mode,
+* benches/ use this directly to time the solver on the real word lists.
+*/
+
+// explicit returns are the house style
+#![allow(clippy::needless_return)]
+
+pub mod absurdle;
+pub mod alphabet;
+pub mod analysis;
+pub mod automated;
+pub mod calendar;
+pub mod cli;
+pub mod engine;
+pub mod player;
+pub mod game;
+pub mod practice;
+pub mod multi;
+pub mod nerdle;
+pub mod output;
+pub mod packed;
+pub mod prior;
+pub mod render;
+pub mod reverse;
+pub mod rng;
+pub mod session;
+#[cfg(feature = "server")]
+pub mod server;
+pub mod share;
+pub mod stats;
+#[cfg(feature = "tui")]
+pub mod tui;
+pub mod wordlist;
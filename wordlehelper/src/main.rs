@@ -5,193 +5,534 @@
 *
 */
 
+// explicit returns are the house style
+#![allow(clippy::needless_return)]
+
 use std::env;
-use std::fs;
+use std::io::Read;
 use std::collections::HashMap;
-use std::collections::HashSet;
-
-mod player;
-mod game;
 
-// receive words, answers, and day. pick answer word. begin loop of calling player, validating guess, and returning guess results
-fn automated(words: &HashSet<&str>, answers: &Vec<&str>, day: &usize) {
-    // grab a word to be the answer
-    let answer = answers[day.clone()];
+use wordlehelper::{absurdle, alphabet, analysis, automated, calendar, cli, engine, game, multi, nerdle, output, player, practice, prior, render, reverse, rng, session, share, stats, wordlist};
+#[cfg(feature = "server")]
+use wordlehelper::server;
+#[cfg(feature = "tui")]
+use wordlehelper::tui;
 
-    // trim the answers to only have old answers. not current or future ones
-    let mut answers = answers.to_vec();
-    answers.resize(day.clone(),&"");
+use cli::{DaySpec, Game, Mode};
+use game::{Feedback, History, Pools};
+use player::Strategy;
+use serde_json::json;
+use wordlist::{Duplicates, WordList};
 
+// interactive only ever sees the answers from before the day being played, so it can't spoil the puzzle.
+// a resumed game starts with the guesses it already had, and save is given the history after every guess.
+// returns every guess and its results so the game can be checked afterwards, and whether the game was finished.
+fn interactive(pools: &Pools, past_answers: &[&str], strategy: Strategy, resumed: Vec<(String,Vec<u8>)>, save: impl Fn(&[(String,Vec<u8>)])) -> (History, bool) {
     // board state tracks all guesses and the results of those guesses.
     // value is a hot encoding where 0 is a miss, 1 is an incorrect position, 2's are correct positions.
     let mut board_state: HashMap<String,Vec<u8>> = HashMap::new();
+    // history keeps the guesses in the order they were played
+    let mut history: Vec<(String,Vec<u8>)> = resumed;
+    // candidates are the possible answers that still fit the board
+    let mut candidates: Vec<&str> = pools.possible.clone();
+    for (guess_word, state_vec) in history.iter() {
+        candidates = game::filter_by_feedback(pools.feedback, &candidates, guess_word, state_vec);
+        board_state.insert(guess_word.clone(), state_vec.clone());
+    }
+    if !history.is_empty() && output::is_text() {
+        println!("{}", render::board(pools.feedback, &history));
+    }
 
     // loop counter keeps track of how many guesses it took
-    let mut loop_counter = 0;
-    // guesses keeps track of all guesses, to be printed to the user
-    let mut guesses: Vec<String> = Vec::new();
-    // loop and check answer
+    let mut loop_counter = history.len();
+    // loop with user input
     loop {
-
-        // get letter frequencies considering position
-        let letter_dist = player::get_letter_frequencies(&words, &board_state);
-
-        // get letter frequencies without considering positions
-        let mut letters: Vec<char> = Vec::new();
-        if loop_counter >= 2{
-            letters = vec![];
-        }
-        else{
-            letters = player::suggest_letters(&words, &loop_counter);
-        }
-
-        // get distance lists for each row
-        let distance_lists = player::get_distance_list(&letter_dist);
-
-        // get a word with either required letters or not depending on the loop_counter
-        let guess_word = player::suggest_word(&words,&distance_lists, &board_state, &answers, letters);
-        guesses.push(guess_word.clone());
-        if guess_word == "".to_string(){
-            println!("failed to guess word {:?}",guesses);
+        // suggest a word
+        let guess_word = player::best_guess(strategy, pools, &candidates, &board_state, past_answers, loop_counter);
+        if guess_word.is_empty(){
+            output::say("No more words left to guess. The answer word is not in the list.");
             break
         }
+        if output::is_text() {
+            println!("guess '{}'", guess_word);
+        }
+        output::record("suggestion", json!({ "puzzle": past_answers.len(), "turn": loop_counter + 1, "guess": guess_word, "candidates": candidates.len() }));
 
         // get board results
-        let state_vec = game::determine_board_results(&answer.to_string(), &guess_word);
+        let word_len = alphabet::letter_count(&guess_word);
+        let state_vec = match read_feedback(pools.feedback, word_len) {
+            Some(state_vec) => state_vec,
+            None => {
+                output::say("out of input. the game is kept to pick up again with --resume");
+                return (history, false);
+            }
+        };
+        history.push((guess_word.clone(),state_vec.clone()));
+        save(&history);
+        if output::is_text() {
+            println!("{}", render::result(pools.feedback, &guess_word, &state_vec));
+            if let Feedback::Colors = pools.feedback {
+                println!("{}", render::keyboard(&history));
+            }
+        }
 
         // update loop counter to match guess count
         loop_counter += 1;
 
+        let remaining = game::filter_by_feedback(pools.feedback, &candidates, &guess_word, &state_vec);
+        output::turn(pools, json!({ "puzzle": past_answers.len() }), loop_counter, &candidates, &guess_word, &state_vec, remaining.len());
+
         // quit if we're successful
-        if state_vec[0] == 2 &&
-           state_vec[1] == 2 &&
-           state_vec[2] == 2 &&
-           state_vec[3] == 2 &&
-           state_vec[4] == 2 {
-            println!("day {} : guessed '{}' in {} guesses. {:?}",day,guess_word,loop_counter,guesses);
-            // statistics print statement.
-//            println!("{},{}",day,loop_counter);
+        if pools.feedback.is_solved(&state_vec, word_len) {
+            output::say("Congratulations.");
             break
         }
 
         // update the board state
+        candidates = remaining;
         board_state.insert(guess_word.clone(),state_vec);
+
+
     }
+
+    let solved = history.last().is_some_and(|(guess_word, state_vec)| pools.feedback.is_solved(state_vec, alphabet::letter_count(guess_word)));
+    let words: Vec<&str> = history.iter().map(|(guess_word, _)| guess_word.as_str()).collect();
+    output::record("game", json!({ "puzzle": past_answers.len(), "solved": solved, "guesses": history.len(), "words": words }));
+    return (history, true);
 }
 
-fn interactive(words: &HashSet<&str>, answers: &Vec<&str>, day: &usize) {
-    // trim the answers to only have old answers. not current or future ones
-    let mut answers = answers.to_vec();
-    answers.resize(day.clone(),&"");
+// ask for the colors, jotto counts or mastermind pegs until they make sense. None once the input runs out
+fn read_feedback(feedback: Feedback, word_len: usize) -> Option<Vec<u8>> {
+    loop {
+        let mut input = String::new();
+        if std::io::stdin().read_line(&mut input).is_ok_and(|read| read == 0) {
+            return None;
+        }
+        match feedback.parse_results(&input, word_len) {
+            Ok(results) => return Some(results),
+            Err(error) => output::say(&error),
+        }
+    }
+}
 
-    // board state tracks all guesses and the results of those guesses.
-    // value is a hot encoding where 0 is a miss, 1 is an incorrect position, 2's are correct positions.
-    let mut board_state: HashMap<String,Vec<u8>> = HashMap::new();
+// reveal the answer after a finished game and score each guess against it.
+// any row where the entered results don't match the real ones is flagged, usually a typo.
+fn check_game(feedback: Feedback, history: &[(String,Vec<u8>)], answer: &str, day: usize) {
+    if !output::is_text() {
+        for (turn, (guess, entered)) in history.iter().enumerate() {
+            let actual = feedback.results(answer, guess);
+            output::record("check", json!({
+                "puzzle": day,
+                "answer": answer,
+                "turn": turn + 1,
+                "guess": guess,
+                "pattern": feedback.describe(&actual),
+                "entered": feedback.describe(entered),
+                "matches": &actual == entered,
+            }));
+        }
+        return;
+    }
+    println!("the answer for {} was '{}'", calendar::describe_day(day), answer);
+    for (guess, entered) in history.iter() {
+        let actual = feedback.results(answer, guess);
+        if &actual == entered {
+            println!("  {}", render::result(feedback, guess, &actual));
+        }
+        else {
+            println!("  {} but you entered {}", render::result(feedback, guess, &actual), render::result(feedback, guess, entered));
+        }
+    }
+    match history.iter().position(|(guess, _)| guess == answer) {
+        Some(i) => println!("solved in {} guesses.", i + 1),
+        None => println!("the answer was never guessed."),
+    }
+}
 
-    // loop counter keeps track of how many guesses it took
-    let mut loop_counter = 0;
-    // loop with user input
-    loop {
-        // get letter frequencies
-        let letter_dist = player::get_letter_frequencies(&words, &board_state);
+// grade a game the user played somewhere else. reads lines like 'crane 00120' until the game is solved or input runs out.
+fn review(pools: &Pools, alphabet: &alphabet::Alphabet, past_answers: &[&str], strategy: Strategy) {
+    output::say("enter each guess and its results like 'crane 00120', finish with an empty line");
+    let mut history: Vec<(String,Vec<u8>)> = Vec::new();
+    let mut input = String::new();
+    while std::io::stdin().read_line(&mut input).is_ok_and(|read| read > 0) {
+        let line = alphabet::normalize(input.trim());
+        input.clear();
+        if line.is_empty() {
+            break
+        }
+        let (guess_word, state_vec) = match player::parse_history_line(&line) {
+            Ok(entry) => entry,
+            Err(error) => {
+                output::say(&error);
+                continue
+            }
+        };
+        // the grading scores the guess against every candidate, so it has to be a word of the list's length and letters
+        let unknown = alphabet.unknown_letters(&guess_word);
+        if !unknown.is_empty() {
+            output::say(&format!("'{}' has letters that aren't in the alphabet: {}", guess_word, unknown.join(" ")));
+            continue
+        }
+        if state_vec.len() != pools.packed.word_len {
+            output::say(&format!("'{}' isn't {} letters long", guess_word, pools.packed.word_len));
+            continue
+        }
+        let solved = game::is_solved(&state_vec);
+        history.push((guess_word, state_vec));
+        if solved {
+            break
+        }
+    }
+    analysis::print_report(&analysis::grade_game(strategy, pools, past_answers, &history));
+}
 
-        // get letter frequencies without considering positions
-        let mut letters: Vec<char> = Vec::new();
-        if loop_counter >= 2{
-            letters = vec![];
+// tell the user if a word was already used as an answer. only days before `day` are searched so nothing is spoiled.
+fn past_answer(words: &[String], answers: &[&str], day: usize) {
+    for word in words.iter() {
+        let word = alphabet::normalize(word);
+        let puzzle = answers[..day].iter().position(|answer| *answer == word);
+        if !output::is_text() {
+            output::record("answer", json!({ "word": word, "puzzle": puzzle }));
+            continue
         }
-        else{
-            letters = player::suggest_letters(&words, &loop_counter);
+        match puzzle {
+            Some(i) => println!("'{}' was the answer to {}", word, calendar::describe_day(i)),
+            None => println!("'{}' has not been an answer before {}", word, calendar::describe_day(day)),
         }
+    }
+}
 
-        // get distance lists for each row
-        let distance_lists = player::get_distance_list(&letter_dist);
+// an interactive game that's saved after every guess so it can be resumed. finished games go in the history of games
+fn play_saved(options: &cli::Options, pools: &Pools, answers: &[&str], day: usize, resumed: Vec<(String,Vec<u8>)>) {
+    let dir = match session::state_dir(options.state_dir.as_deref()) {
+        Ok(dir) => Some(dir),
+        Err(error) => {
+            output::say(&format!("warning: {}. the game won't be saved", error));
+            None
+        }
+    };
+    let session = |history: &[(String,Vec<u8>)]| session::Session {
+        game: options.game,
+        puzzle: day,
+        strategy: options.strategy,
+        feedback: options.feedback,
+        history: history.to_vec(),
+    };
+    let save = |history: &[(String,Vec<u8>)]| {
+        if let Some(Err(error)) = dir.as_ref().map(|dir| session(history).save(dir)) {
+            output::say(&format!("warning: {}", error));
+        }
+    };
 
-        // suggest a word
-        let guess_word = player::suggest_word(&words,&distance_lists, &board_state, &answers, letters);
-        if guess_word == "".to_string(){
-            println!("No more words left to guess. The answer word is not in the list.");
-            break
+    // the full screen board can be closed part way through, that game is kept to resume later
+    let (history, finished) = if options.tui {
+        match tui(pools, &answers[..day], options.strategy, resumed, save) {
+            Some(played) => played,
+            None => return,
         }
-        println!("guess '{}'", guess_word);
+    }
+    else {
+        interactive(pools, &answers[..day], options.strategy, resumed, save)
+    };
+    // a game left to resume isn't shared or checked, that would give the answer away
+    if !finished {
+        return;
+    }
+    if let Some(dir) = dir.as_ref() {
+        let solved = history.last().is_some_and(|(guess_word, results)| pools.feedback.is_solved(results, alphabet::letter_count(guess_word)));
+        if let Err(error) = session(&history).finish(dir, solved) {
+            output::say(&format!("warning: {}", error));
+        }
+    }
 
-        // get board results
-        let state_vec = player::get_board_results();
+    if let (Feedback::Colors, true) = (pools.feedback, output::is_text()) {
+        println!("{}", share::emoji_grid(options.game, day, &history));
+    }
+    if options.check {
+        check_game(pools.feedback, &history, answers[day], day);
+    }
+}
 
-        // update loop counter to match guess count
-        loop_counter += 1;
+// stats for the finished games of the game and feedback being played, with the solver replaying each puzzle
+fn personal_stats(options: &cli::Options, pools: &Pools, answers: &[&str]) {
+    let dir = match session::state_dir(options.state_dir.as_deref()) {
+        Ok(dir) => dir,
+        Err(error) => {
+            output::say(&error);
+            return;
+        }
+    };
+    let (finished, broken) = match session::load_finished(&dir) {
+        Ok(loaded) => loaded,
+        Err(error) => {
+            output::say(&error);
+            return;
+        }
+    };
+    if broken > 0 {
+        output::say(&format!("warning: {} of the lines in {} couldn't be read", broken, dir.join(session::GAMES_FILE).display()));
+    }
+    let games: Vec<&session::FinishedGame> = finished.iter()
+        .filter(|finished_game| finished_game.game == options.game.name() && finished_game.feedback == options.feedback.name())
+        .collect();
+    if games.is_empty() {
+        output::say(&format!("There are no finished {} games in {} yet", options.game.name(), dir.display()));
+        return;
+    }
 
-        // quit if we're successful
-        if state_vec[0] == 2 &&
-           state_vec[1] == 2 &&
-           state_vec[2] == 2 &&
-           state_vec[3] == 2 &&
-           state_vec[4] == 2 {
-            println!("Congratulations.");
-            break()
+    // a puzzle played twice is only solved once
+    let mut opener = None;
+    let mut solver_games: HashMap<usize, Option<usize>> = HashMap::new();
+    let solver: Vec<Option<usize>> = games.iter().map(|finished_game| {
+        if finished_game.puzzle >= answers.len() {
+            return None;
         }
+        return *solver_games.entry(finished_game.puzzle).or_insert_with(|| {
+            let history = automated::play(pools, answers, finished_game.puzzle, options.strategy, &mut opener, true);
+            let solved = history.last().is_some_and(|(guess_word, _)| guess_word == answers[finished_game.puzzle]);
+            solved.then_some(history.len())
+        });
+    }).collect();
+    stats::print_stats(&stats::summarize(&games, &solver));
+}
 
-        // update the board state
-        board_state.insert(guess_word.clone(),state_vec);
+#[cfg(feature = "server")]
+fn serve(pools: &Pools, alphabet: &alphabet::Alphabet, strategy: Strategy, port: u16) {
+    if let Err(error) = server::serve(pools, alphabet, strategy, port) {
+        output::say(&error);
+    }
+}
 
+#[cfg(not(feature = "server"))]
+fn serve(_pools: &Pools, _alphabet: &alphabet::Alphabet, _strategy: Strategy, _port: u16) {
+    output::say("This build doesn't have the server. Build it with --features server");
+}
 
+// the game on the full screen board, and whether it was finished
+#[cfg(feature = "tui")]
+fn tui(pools: &Pools, past_answers: &[&str], strategy: Strategy, resumed: History, save: impl Fn(&[(String,Vec<u8>)])) -> Option<(History, bool)> {
+    return match tui::play(pools, past_answers, strategy, resumed, save) {
+        Ok(played) => Some(played),
+        Err(error) => {
+            output::say(&error);
+            None
+        }
+    };
+}
+
+#[cfg(not(feature = "tui"))]
+fn tui(_pools: &Pools, _past_answers: &[&str], _strategy: Strategy, _resumed: History, _save: impl Fn(&[(String,Vec<u8>)])) -> Option<(History, bool)> {
+    output::say("This build doesn't have the full screen board. Build it with --features tui");
+    return None;
+}
+
+// the allowed guesses and the answers. nerdle makes its equations up instead of reading files
+fn load_lists(options: &cli::Options) -> Result<(WordList, WordList), String> {
+    if let Game::Nerdle = options.game {
+        return Ok((nerdle::word_list(), nerdle::word_list()));
     }
+
+    // get words in file
+    let word_list = wordlist::load_or_embedded(options.word_file.as_deref(), wordlist::EMBEDDED_WORDS, Duplicates::Drop, &alphabet::Alphabet::english())?;
+
+    // get answer list so that we can exclude previous answers from our guesses.
+    // the order matters here, line n is the answer to puzzle n. the answers use the same letters as the guesses
+    let answer_list = wordlist::load_or_embedded(options.answer_file.as_deref(), wordlist::EMBEDDED_ANSWERS, Duplicates::Keep, &word_list.alphabet)?;
+    return Ok((word_list, answer_list));
 }
 
 // get the word list, suggest word to player, get board state update from player.
 fn main() {
     let args: Vec<String> = env::args().collect();
-    // sanity check that we have enough args
-    if args.len() < 4{
-        println!("Not enough args, run like $ ./wordlehelper ../../words/wordle_words.txt ../../words/ny_times_answers.txt 257 i");
-        return;
-    }
-    
-    // get words in file
-    let word_file = &args[1];
-    let words_s = fs::read_to_string(word_file);
-    let words_s = match words_s {
-        Ok(words_s) => words_s,
-        Err(error) => panic!("Could not open word file: {:?}", error)
+    let options = match cli::parse_args(&args) {
+        Ok(options) => options,
+        Err(error) => {
+            println!("{}", error);
+            return;
+        }
     };
-    // split to hashset of str's
-    let words: HashSet<&str> = words_s.split("\n").collect();
+    render::init(&options.color);
+    output::init(options.format);
+    run(options);
+    output::finish();
+}
 
-    // get answer list so that we can exclude previous answers from our guesses.
-    let answer_file = &args[2];
-    let answer_s = fs::read_to_string(answer_file);
-    let answer_s = match answer_s {
-        Ok(a) => a,
-        Err(error) => panic!("Could not open answer file: {:?}", error)
+// everything after the args are read. anything that returns early still gets its records printed
+fn run(mut options: cli::Options) {
+    // a resumed game brings back the puzzle and settings it was started with
+    let mut resumed: Vec<(String,Vec<u8>)> = Vec::new();
+    if options.resume {
+        let saved = session::state_dir(options.state_dir.as_deref()).and_then(|dir| session::Session::load(&dir));
+        match saved {
+            Ok(saved) => {
+                output::say(&format!("resuming puzzle {} after {} guesses", saved.puzzle, saved.history.len()));
+                options.game = saved.game;
+                options.day = DaySpec::Puzzle(saved.puzzle);
+                options.strategy = saved.strategy;
+                options.feedback = saved.feedback;
+                resumed = saved.history;
+            }
+            Err(error) => {
+                output::say(&error);
+                return;
+            }
+        }
+    }
+
+    let (word_list, answer_list) = match load_lists(&options) {
+        Ok(lists) => lists,
+        Err(error) => {
+            output::say(&error);
+            return;
+        }
     };
+    wordlist::report(&word_list);
+    wordlist::report(&answer_list);
+    let words: Vec<&str> = word_list.words.iter().map(|word| word.as_str()).collect();
+    let answers: Vec<&str> = answer_list.words.iter().map(|word| word.as_str()).collect();
 
-    // get the day that we're playing to trim down the answer list.
-    let day_a = &args[3];
-    let day = match day_a.parse::<usize>() {
-        Ok(a) => a,
-        Err(error) => panic!("Could not parse day: {:?}", error)
+    // weights for how likely each word is to be the answer, every word is equally likely without one
+    let prior = match options.prior_file.as_deref().map(prior::Prior::load) {
+        Some(Ok(prior)) => prior,
+        Some(Err(error)) => {
+            output::say(&error);
+            return;
+        }
+        None => prior::Prior::uniform(),
     };
-    // reduce to a vec of old answers according to the day
-    let answers: Vec<&str> = answer_s.split("\n").collect();
 
-    let mode = &args[4];
+    // guesses can be any allowed word, the answer is always one of the possible answers.
+    // when the answer list isn't known every allowed word might be the answer
+    let possible = if options.unknown_answers { &words } else { &answers };
+    let pools = Pools::new(&words, possible, prior, options.feedback);
+
+    // automated mode can play every day in the list, everything else needs a single day.
+    if let (Mode::Automated, DaySpec::All) = (&options.mode, &options.day) {
+//        println!("day,guesses");
+        let mut opener = None;
+        for i in 0..answers.len(){
+            automated::play(&pools, &answers, i, options.strategy, &mut opener, false);
+        }
+        return;
+    }
 
-    if mode == &"a"{
-        if day == 10000{
-//            println!("day,guesses");
-            for i in 0..answers.len(){
-                automated(&words, &answers, &i);
+    // stats go over every finished game, not a day
+    if let Mode::Stats = options.mode {
+        personal_stats(&options, &pools, &answers);
+        return;
+    }
+
+    // the engine follows commands on stdin until it's told to quit
+    if let Mode::Engine = options.mode {
+        if let Err(error) = engine::run(&pools, options.strategy, std::io::stdin().lock(), std::io::stdout()) {
+            output::say(&error);
+        }
+        return;
+    }
+
+    // the server answers requests until it's stopped
+    if let Mode::Serve = options.mode {
+        serve(&pools, &word_list.alphabet, options.strategy, options.port);
+        return;
+    }
+
+    // the absurdle host has no answer list order or day, only candidates
+    if let Mode::Absurdle = options.mode {
+        absurdle::play(&pools, options.strategy);
+        return;
+    }
+    if let (Mode::Automated, true) = (&options.mode, options.absurdle) {
+        absurdle::print_game(&pools, &absurdle::solve(&pools, options.strategy));
+        return;
+    }
+
+    // multi board games use random answers from the whole answer list instead of a day
+    if options.boards > 1 {
+        match options.mode {
+            Mode::Automated => {
+                let seed = options.seed.unwrap_or_else(rng::Rng::random_seed);
+                multi::automated(&pools, options.boards, options.games, seed);
             }
+            Mode::Interactive => multi::interactive(&pools, options.boards),
+            _ => output::say("--boards only works with modes 'a' and 'i'"),
         }
-        else {
-            automated(&words, &answers, &day);
+        return;
+    }
+
+    // practice picks its own answer, so the day doesn't matter
+    if let Mode::Practice = options.mode {
+        let seed = options.seed.unwrap_or_else(rng::Rng::random_seed);
+        if let Err(error) = practice::practice(options.game, &pools, &answers, seed, options.strategy) {
+            output::say(&error);
         }
+        return;
     }
-    else if mode == &"i" {
-        println!("answer is '{}'",answers[day]);
-        interactive(&words, &answers, &day);
+
+    // a share already says which puzzle it's for
+    if let Mode::ShareImport = options.mode {
+        let mut text = String::new();
+        let imported = std::io::stdin().read_to_string(&mut text)
+            .map_err(|error| format!("Could not read the share: {:?}", error))
+            .and_then(|_| share::parse_share(&text))
+            .and_then(|parsed| share::import(&pools.allowed, &answers, &parsed));
+        if let Err(error) = imported {
+            output::say(&error);
+        }
+        return;
     }
-    else {
-        println!("Invalid game mode. Please use 'a' or 'i'.")
+
+    // the reverse solver works without knowing the day, only the posted grids
+    if let Mode::Deduce = options.mode {
+        let mut text = String::new();
+        let shares = std::io::stdin().read_to_string(&mut text)
+            .map_err(|error| format!("Could not read the shares: {:?}", error))
+            .and_then(|_| share::parse_shares(&text));
+        let shares = match shares {
+            Ok(shares) => shares,
+            Err(error) => {
+                output::say(&error);
+                return;
+            }
+        };
+        if shares.iter().any(|parsed| parsed.puzzle != shares[0].puzzle) {
+            output::say("warning: the shares are for different puzzles");
+        }
+        let patterns = match reverse::posted_patterns(&shares, pools.packed.word_len) {
+            Ok(patterns) => patterns,
+            Err(error) => {
+                output::say(&error);
+                return;
+            }
+        };
+        let guesses: Vec<&str> = pools.allowed.iter().copied().collect();
+        let matrix = game::PatternMatrix::new(&guesses, &pools.possible);
+        reverse::print_ranking(&reverse::rank_answers(&matrix, &patterns), patterns.len(), options.top);
+        return;
+    }
+
+    // get the day that we're playing to trim down the answer list.
+    let day = match options.day.resolve(answers.len()) {
+        Ok(day) => day,
+        Err(error) => {
+            output::say(&error);
+            return;
+        }
+    };
+
+    match options.mode {
+        Mode::Automated => {
+            let history = automated::play(&pools, &answers, day, options.strategy, &mut None, false);
+            if output::is_text() {
+                println!("{}", render::board(pools.feedback, &history));
+                // only the colors make a share grid
+                if let Feedback::Colors = pools.feedback {
+                    println!("{}", share::emoji_grid(options.game, day, &history));
+                }
+            }
+        }
+        Mode::Interactive => play_saved(&options, &pools, &answers, day, resumed),
+        Mode::PastAnswer(ref past_words) => past_answer(past_words, &answers, day),
+        Mode::Review => review(&pools, &word_list.alphabet, &answers[..day], options.strategy),
+        Mode::Practice | Mode::ShareImport | Mode::Deduce | Mode::Absurdle | Mode::Stats | Mode::Engine | Mode::Serve => unreachable!("these modes are handled before the day is resolved"),
     }
 }
@@ -0,0 +1,258 @@
+/*
+* multi board games like dordle (2), quordle (4) and octordle (8). every guess is played on all
+* of the boards that aren't solved yet, and you get a few more guesses than boards to solve them all.
+*/
+
+use std::io;
+
+use serde_json::{json, Value};
+
+use crate::alphabet;
+use crate::game;
+use crate::game::Pools;
+use crate::output;
+use crate::player;
+use crate::render;
+use crate::rng::Rng;
+
+// one answer's board. candidates are the possible answers that still fit it
+pub struct Board<'a> {
+    pub candidates: Vec<&'a str>,
+    // the guess number that solved it
+    pub solved_on: Option<usize>,
+    pub history: Vec<(String,Vec<u8>)>,
+}
+
+pub struct MultiGame<'a> {
+    pub boards: Vec<Board<'a>>,
+    pub guesses: Vec<String>,
+}
+
+impl<'a> MultiGame<'a> {
+    pub fn new(board_count: usize, possible: &[&'a str]) -> MultiGame<'a> {
+        let boards = (0..board_count).map(|_| Board { candidates: possible.to_vec(), solved_on: None, history: Vec::new() }).collect();
+        return MultiGame { boards, guesses: Vec::new() };
+    }
+
+    // dordle gives 7 guesses, quordle 9 and octordle 13
+    pub fn max_guesses(&self) -> usize {
+        return self.boards.len() + 5;
+    }
+
+    pub fn is_won(&self) -> bool {
+        return self.boards.iter().all(|board| board.solved_on.is_some());
+    }
+
+    pub fn is_over(&self) -> bool {
+        return self.is_won() || self.guesses.len() >= self.max_guesses();
+    }
+
+    // play a guess. results has one entry per board, boards that are already solved ignore theirs
+    pub fn apply(&mut self, guess_word: &str, results: &[Vec<u8>]) {
+        self.guesses.push(guess_word.to_string());
+        let turn = self.guesses.len();
+        for (board, state_vec) in self.boards.iter_mut().zip(results.iter()) {
+            if board.solved_on.is_some() {
+                continue
+            }
+            board.candidates = game::filter_candidates(&board.candidates, guess_word, state_vec);
+            board.history.push((guess_word.to_string(), state_vec.clone()));
+            if game::is_solved(state_vec) {
+                board.solved_on = Some(turn);
+            }
+        }
+    }
+}
+
+// the host side, score a guess against every board's answer
+pub fn determine_results(answers: &[&str], guess_word: &str) -> Vec<Vec<u8>> {
+    return answers.iter().map(|answer| game::determine_board_results(answer, guess_word)).collect();
+}
+
+// the guess that learns the most across every unsolved board, adding up each board's expected information.
+// a board that's down to one word gets guessed straight away since that's a free solve.
+pub fn best_guess(pools: &Pools, multi_game: &MultiGame) -> String {
+    let open: Vec<&Board> = multi_game.boards.iter()
+        .filter(|board| board.solved_on.is_none() && !board.candidates.is_empty())
+        .collect();
+    if let Some(board) = open.iter().find(|board| board.candidates.len() == 1) {
+        return board.candidates[0].to_string();
+    }
+
+    let mut best: Option<(f64, bool, &str)> = None;
+    for guess_word in pools.allowed.iter() {
+        let bits: f64 = open.iter().map(|board| player::expected_information(pools, &board.candidates, guess_word)).sum();
+        // words that could solve a board win ties
+        let entry = (bits, open.iter().any(|board| board.candidates.contains(guess_word)), *guess_word);
+        best = match best {
+            Some(current) if (current.0, current.1) >= (entry.0, entry.1) => Some(current),
+            _ => Some(entry),
+        };
+    }
+    return best.map(|(_, _, word)| word.to_string()).unwrap_or_default();
+}
+
+// play one game against known answers. the opener is the same every game so it's only worked out once
+fn play<'a>(pools: &Pools<'a>, answers: &[&str], opener: &mut Option<String>) -> MultiGame<'a> {
+    let mut multi_game = MultiGame::new(answers.len(), &pools.possible);
+    while !multi_game.is_over() {
+        let guess_word = match (multi_game.guesses.len(), opener.as_ref()) {
+            (0, Some(opener)) => opener.clone(),
+            _ => best_guess(pools, &multi_game),
+        };
+        if multi_game.guesses.is_empty() {
+            *opener = Some(guess_word.clone());
+        }
+        if guess_word.is_empty() {
+            break
+        }
+        multi_game.apply(&guess_word, &determine_results(answers, &guess_word));
+    }
+    return multi_game;
+}
+
+// benchmark the solver on random sets of answers
+pub fn automated(pools: &Pools, board_count: usize, games: usize, seed: u64) {
+    let mut rng = Rng::new(seed);
+    let mut opener = None;
+    let mut solved_in: Vec<usize> = Vec::new();
+    let mut failed = 0;
+    output::say(&format!("{} boards, {} games (seed {})", board_count, games, seed));
+    for game_number in 0..games {
+        // each board gets a different answer
+        let mut answers: Vec<&str> = Vec::new();
+        while answers.len() < board_count.min(pools.possible.len()) {
+            let answer = pools.possible[rng.below(pools.possible.len())];
+            if !answers.contains(&answer) {
+                answers.push(answer);
+            }
+        }
+
+        let multi_game = play(pools, &answers, &mut opener);
+        record_game(pools, json!({ "seed": seed, "game": game_number }), &multi_game);
+        output::record("game", json!({
+            "seed": seed,
+            "game": game_number,
+            "answers": answers,
+            "solved": multi_game.is_won(),
+            "guesses": multi_game.guesses.len(),
+            "words": multi_game.guesses,
+        }));
+        if multi_game.is_won() {
+            if output::is_text() {
+                println!("game {} : guessed {:?} in {} guesses. {:?}", game_number, answers, multi_game.guesses.len(), multi_game.guesses);
+            }
+            solved_in.push(multi_game.guesses.len());
+        }
+        else {
+            if output::is_text() {
+                println!("game {} : failed {:?} {:?}", game_number, answers, multi_game.guesses);
+            }
+            failed += 1;
+        }
+    }
+    let average = if solved_in.is_empty() { 0.0 } else { solved_in.iter().sum::<usize>() as f64 / solved_in.len() as f64 };
+    let worst = solved_in.iter().max().copied().unwrap_or(0);
+    output::record("summary", json!({ "seed": seed, "boards": board_count, "games": games, "solved": solved_in.len(), "average": average, "worst": worst }));
+    if !output::is_text() {
+        return;
+    }
+    if !solved_in.is_empty() {
+        println!("solved {}/{}, average {:.3} guesses, worst {}", solved_in.len(), games, average, worst);
+    }
+    else {
+        println!("solved 0/{}, {} failed", games, failed);
+    }
+}
+
+// a turn record for every guess on every board, replayed from the boards' histories
+fn record_game(pools: &Pools, game: Value, multi_game: &MultiGame) {
+    if output::is_text() {
+        return;
+    }
+    for (i, board) in multi_game.boards.iter().enumerate() {
+        let mut candidates: Vec<&str> = pools.possible.clone();
+        for (turn, (guess_word, state_vec)) in board.history.iter().enumerate() {
+            let remaining = game::filter_candidates(&candidates, guess_word, state_vec);
+            let mut fields = game.clone();
+            fields["board"] = json!(i + 1);
+            output::turn(pools, fields, turn + 1, &candidates, guess_word, state_vec, remaining.len());
+            candidates = remaining;
+        }
+    }
+}
+
+// suggest guesses and ask for the results on each unsolved board
+pub fn interactive(pools: &Pools, board_count: usize) {
+    let mut multi_game = MultiGame::new(board_count, &pools.possible);
+    while !multi_game.is_over() {
+        let guess_word = best_guess(pools, &multi_game);
+        if guess_word.is_empty() {
+            output::say("No more words left to guess. An answer word is not in the list.");
+            return;
+        }
+        if output::is_text() {
+            println!("guess '{}'", guess_word);
+        }
+        output::record("suggestion", json!({ "turn": multi_game.guesses.len() + 1, "guess": guess_word }));
+
+        let mut results: Vec<Vec<u8>> = Vec::new();
+        for (i, board) in multi_game.boards.iter().enumerate() {
+            if board.solved_on.is_some() {
+                results.push(Vec::new());
+                continue
+            }
+            match read_results(i + 1, alphabet::letter_count(&guess_word)) {
+                Some(state_vec) => results.push(state_vec),
+                None => {
+                    output::say("out of input. the game ends here");
+                    return;
+                }
+            }
+        }
+        let before: Vec<Vec<&str>> = multi_game.boards.iter().map(|board| board.candidates.clone()).collect();
+        multi_game.apply(&guess_word, &results);
+
+        for (i, board) in multi_game.boards.iter().enumerate() {
+            let (last_guess, state_vec) = match board.history.last() {
+                Some(last) => last,
+                None => continue,
+            };
+            if !output::is_text() {
+                // boards solved earlier didn't play this guess
+                if board.solved_on.is_none_or(|turn| turn == multi_game.guesses.len()) {
+                    output::turn(pools, json!({ "board": i + 1 }), multi_game.guesses.len(), &before[i], last_guess, state_vec, board.candidates.len());
+                }
+                continue
+            }
+            let note = match board.solved_on {
+                Some(turn) => format!("solved on guess {}", turn),
+                None => format!("{} left", board.candidates.len()),
+            };
+            println!("board {}: {} {}", i + 1, render::tiles(last_guess, state_vec), note);
+        }
+    }
+    output::record("game", json!({ "boards": board_count, "solved": multi_game.is_won(), "guesses": multi_game.guesses.len(), "words": multi_game.guesses }));
+    if multi_game.is_won() {
+        output::say(&format!("Congratulations. solved every board in {} guesses.", multi_game.guesses.len()));
+    }
+    else {
+        output::say("out of guesses.");
+    }
+}
+
+// keep asking until we get results of the right length for a board. None when the input runs out
+fn read_results(board_number: usize, word_len: usize) -> Option<Vec<u8>> {
+    loop {
+        output::say(&format!("board {} results:", board_number));
+        let mut input = String::new();
+        if io::stdin().read_line(&mut input).is_ok_and(|read| read == 0) {
+            return None;
+        }
+        match game::parse_colors(&input) {
+            Ok(state_vec) if state_vec.len() == word_len => return Some(state_vec),
+            Ok(_) => output::say(&format!("results need to be {} long", word_len)),
+            Err(error) => output::say(&error),
+        }
+    }
+}
@@ -0,0 +1,197 @@
+/*
+* nerdle, wordle with sums. every guess is an 8 symbol equation like '12+35=47' made of digits and
+* + - * / =, and has to be true. there's no word list, the dictionary is every valid equation, worked out here.
+* the rules: the left side is numbers joined by at least one operator and worked out in the usual order
+* (* and / before + and -), the right side is a single whole number that isn't negative.
+* numbers can't have leading zeros and there are no lone zeros on the left, but the right side can be 0.
+* divisions can go through fractions on the way, like 3/2*4=6, as long as the answer is whole.
+* that makes 17723 equations, the same as the real game.
+*/
+
+use crate::alphabet::Alphabet;
+use crate::wordlist::WordList;
+
+pub const EQUATION_LEN: usize = 8;
+pub const SYMBOLS: &str = "0123456789+-*/=";
+
+const OPERATORS: [u8; 4] = [b'+', b'-', b'*', b'/'];
+
+// every valid equation, sorted so the list is the same every time
+pub fn generate() -> Vec<String> {
+    let mut equations: Vec<String> = Vec::new();
+    // the right side needs at least one digit and the '=' takes one spot
+    for left_len in 3..EQUATION_LEN - 1 {
+        let right_len = EQUATION_LEN - 1 - left_len;
+        let mut left: Vec<u8> = Vec::new();
+        add_number(&mut left, left_len, right_len, &mut equations);
+    }
+    equations.sort();
+    return equations;
+}
+
+// the equations as a word list, so they can be both the guesses and the answers
+pub fn word_list() -> WordList {
+    return WordList { source: "nerdle equations".to_string(), alphabet: Alphabet::parse(SYMBOLS), words: generate(), problems: Vec::new() };
+}
+
+// grow the left side a number at a time, then an operator, until it's the right length
+fn add_number(left: &mut Vec<u8>, left_len: usize, right_len: usize, equations: &mut Vec<String>) {
+    let room = left_len - left.len();
+    for digits in 1..=room {
+        let start = 10u64.pow(digits as u32 - 1);
+        for number in start..10u64.pow(digits as u32) {
+            let before = left.len();
+            left.extend(number.to_string().bytes());
+            if left.len() == left_len {
+                check(left, right_len, equations);
+            }
+            // an operator needs a number after it, so there has to be room for two more symbols
+            else if left.len() + 2 <= left_len {
+                for operator in OPERATORS.iter() {
+                    left.push(*operator);
+                    add_number(left, left_len, right_len, equations);
+                    left.pop();
+                }
+            }
+            left.truncate(before);
+        }
+    }
+}
+
+fn check(left: &[u8], right_len: usize, equations: &mut Vec<String>) {
+    if !left.iter().any(|symbol| OPERATORS.contains(symbol)) {
+        return;
+    }
+    if let Some(value) = evaluate(left) {
+        let right = value.to_string();
+        if right.len() == right_len {
+            equations.push(format!("{}={}", String::from_utf8_lossy(left), right));
+        }
+    }
+}
+
+// digits only, and no leading zero
+fn is_number(text: &str) -> bool {
+    return !text.is_empty() && text.bytes().all(|symbol| symbol.is_ascii_digit()) && (text == "0" || !text.starts_with('0'));
+}
+
+// a number on the left side, where 0 isn't allowed
+fn parse_number(digits: &[u8]) -> Option<Fraction> {
+    let text = std::str::from_utf8(digits).ok()?;
+    if !is_number(text) || text == "0" {
+        return None;
+    }
+    return Some(Fraction::whole(text.parse().ok()?));
+}
+
+// exact sums, so 3/2*4 comes out as 6
+#[derive(Clone, Copy)]
+struct Fraction {
+    top: i64,
+    bottom: i64,
+}
+
+impl Fraction {
+    fn whole(value: i64) -> Fraction {
+        return Fraction { top: value, bottom: 1 };
+    }
+
+    fn new(top: i64, bottom: i64) -> Fraction {
+        let divisor = gcd(top.abs(), bottom.abs()).max(1);
+        let sign = if bottom < 0 { -1 } else { 1 };
+        return Fraction { top: sign * top / divisor, bottom: sign * bottom / divisor };
+    }
+
+    fn add(self, other: Fraction, sign: i64) -> Fraction {
+        return Fraction::new(self.top * other.bottom + sign * other.top * self.bottom, self.bottom * other.bottom);
+    }
+
+    fn mul(self, other: Fraction) -> Fraction {
+        return Fraction::new(self.top * other.top, self.bottom * other.bottom);
+    }
+
+    fn div(self, other: Fraction) -> Fraction {
+        return Fraction::new(self.top * other.bottom, self.bottom * other.top);
+    }
+}
+
+fn gcd(a: i64, b: i64) -> i64 {
+    return if b == 0 { a } else { gcd(b, a % b) };
+}
+
+// work out the left side. None if it isn't a whole number that's zero or more, or doesn't parse
+fn evaluate(left: &[u8]) -> Option<i64> {
+    let mut numbers: Vec<Fraction> = Vec::new();
+    let mut operators: Vec<u8> = Vec::new();
+    let mut start = 0;
+    for (i, symbol) in left.iter().enumerate() {
+        if OPERATORS.contains(symbol) {
+            numbers.push(parse_number(&left[start..i])?);
+            operators.push(*symbol);
+            start = i + 1;
+        }
+    }
+    numbers.push(parse_number(&left[start..])?);
+
+    // * and / first, left to right
+    // numbers are never 0, so there's no dividing by zero
+    let mut terms: Vec<Fraction> = vec![numbers[0]];
+    let mut signs: Vec<i64> = Vec::new();
+    for (operator, number) in operators.iter().zip(numbers[1..].iter()) {
+        let term = terms.last_mut()?;
+        match operator {
+            b'*' => *term = term.mul(*number),
+            b'/' => *term = term.div(*number),
+            b'+' | b'-' => {
+                signs.push(if *operator == b'+' { 1 } else { -1 });
+                terms.push(*number);
+            }
+            _ => return None,
+        }
+    }
+    let mut value = terms[0];
+    for (sign, term) in signs.iter().zip(terms[1..].iter()) {
+        value = value.add(*term, *sign);
+    }
+    return if value.bottom == 1 && value.top >= 0 { Some(value.top) } else { None };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generates_every_equation() {
+        let equations = generate();
+        assert_eq!(equations.len(), 17723);
+        assert!(equations.iter().all(|equation| equation.len() == EQUATION_LEN));
+        assert!(equations.windows(2).all(|pair| pair[0] < pair[1]));
+        // going through a fraction or below zero on the way is fine
+        for equation in ["12+35=47", "12+2-6=8", "3/2*8=12", "10-2*5=0", "9*8-7=65", "2-5+10=7"] {
+            assert!(equations.binary_search(&equation.to_string()).is_ok(), "{} is missing", equation);
+        }
+        // leading zero, lone zero, wrong sum, not whole, negative, operator on the right, worked out left to right
+        for equation in ["01+23=24", "12-0-3=9", "12+35=48", "10/3*2=6", "2*5-12=0", "1+2=3+00", "2+3*4=20"] {
+            assert!(equations.binary_search(&equation.to_string()).is_err(), "{} shouldn't be there", equation);
+        }
+    }
+
+    #[test]
+    fn evaluates_in_the_usual_order() {
+        assert_eq!(evaluate(b"12+35"), Some(47));
+        assert_eq!(evaluate(b"2+3*4"), Some(14));
+        assert_eq!(evaluate(b"10-2*5"), Some(0));
+        assert_eq!(evaluate(b"3/2*4"), Some(6));
+        assert_eq!(evaluate(b"8/4/2"), Some(1));
+    }
+
+    #[test]
+    fn rejects_bad_left_sides() {
+        // not whole, negative, leading zero, lone zero, dangling operator
+        assert_eq!(evaluate(b"7/2"), None);
+        assert_eq!(evaluate(b"2-5"), None);
+        assert_eq!(evaluate(b"01+2"), None);
+        assert_eq!(evaluate(b"5-0"), None);
+        assert_eq!(evaluate(b"5+"), None);
+    }
+}
@@ -0,0 +1,174 @@
+/*
+* machine readable output for scripts, picked with --format. text is the normal output for people.
+* with json, jsonl or csv stdout only has records, and anything meant for a person (prompts, warnings,
+* errors) goes to stderr instead so it can't break the parsing.
+*   jsonl  one object per line, written as it happens
+*   json   one array of every record, written when the program finishes
+*   csv    one header row and then a row per record, written when the program finishes. the header has
+*          every field any record had, and a record leaves the fields it doesn't have empty
+* every record starts with a "record" field saying what it is, like "turn" or "game".
+*/
+
+use std::sync::Mutex;
+
+use serde_json::{json, Map, Value};
+
+use crate::game::Pools;
+use crate::player;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Format {
+    Text,
+    Json,
+    Jsonl,
+    Csv,
+}
+
+impl Format {
+    pub fn parse(name: &str) -> Result<Format, String> {
+        return match name {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "jsonl" => Ok(Format::Jsonl),
+            "csv" => Ok(Format::Csv),
+            _ => Err(format!("Unknown format '{}'. Use json, jsonl, csv or text", name)),
+        };
+    }
+}
+
+struct State {
+    format: Format,
+    // json and csv hold everything back until finish
+    records: Vec<Value>,
+}
+
+static STATE: Mutex<State> = Mutex::new(State { format: Format::Text, records: Vec::new() });
+
+fn state() -> std::sync::MutexGuard<'static, State> {
+    // a panic while printing doesn't leave the state half written, so a poisoned lock is still fine to use
+    return STATE.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+}
+
+// decide once at startup how to print
+pub fn init(format: Format) {
+    state().format = format;
+}
+
+pub fn is_text() -> bool {
+    return state().format == Format::Text;
+}
+
+// a line for a person. it's stdout for text and stderr when stdout has records
+pub fn say(text: &str) {
+    if is_text() {
+        println!("{}", text);
+    }
+    else {
+        eprintln!("{}", text);
+    }
+}
+
+// write a record. it's dropped for text, which prints its own way. fields are in the order given
+pub fn record(kind: &str, fields: Value) {
+    let mut object = Map::new();
+    object.insert("record".to_string(), Value::from(kind));
+    if let Value::Object(fields) = fields {
+        object.extend(fields);
+    }
+    let object = Value::Object(object);
+
+    let mut state = state();
+    match state.format {
+        Format::Text => {}
+        Format::Json | Format::Csv => state.records.push(object),
+        Format::Jsonl => println!("{}", object),
+    }
+}
+
+// one guess in a game. game has the fields that say which game it was, like the puzzle number.
+// the score is the guess's expected information in bits against the candidates it was played on
+pub fn turn(pools: &Pools, game: Value, turn: usize, candidates: &[&str], guess_word: &str, results: &[u8], remaining: usize) {
+    if is_text() {
+        return;
+    }
+    let bits = player::expected_information(pools, candidates, guess_word);
+    let mut fields = match game {
+        Value::Object(fields) => fields,
+        _ => Map::new(),
+    };
+    fields.insert("turn".to_string(), json!(turn));
+    fields.insert("guess".to_string(), json!(guess_word));
+    fields.insert("pattern".to_string(), json!(pools.feedback.describe(results)));
+    fields.insert("remaining".to_string(), json!(remaining));
+    fields.insert("score".to_string(), json!((bits * 1000.0).round() / 1000.0));
+    record("turn", Value::Object(fields));
+}
+
+// print anything held back. called once at the end of main
+pub fn finish() {
+    let mut state = state();
+    let records = std::mem::take(&mut state.records);
+    match state.format {
+        Format::Json => println!("{}", serde_json::to_string_pretty(&Value::Array(records)).unwrap_or_default()),
+        Format::Csv => csv_lines(&records).iter().for_each(|line| println!("{}", line)),
+        Format::Text | Format::Jsonl => {}
+    }
+}
+
+// the header and then a row per record. the columns are every field in the order they first showed up
+fn csv_lines(records: &[Value]) -> Vec<String> {
+    if records.is_empty() {
+        return Vec::new();
+    }
+    let mut header: Vec<&str> = Vec::new();
+    for object in records.iter().filter_map(|record| record.as_object()) {
+        for field in object.keys() {
+            if !header.contains(&field.as_str()) {
+                header.push(field);
+            }
+        }
+    }
+    let mut lines = vec![header.join(",")];
+    for object in records.iter().filter_map(|record| record.as_object()) {
+        let row: Vec<String> = header.iter().map(|field| object.get(*field).map(csv_cell).unwrap_or_default()).collect();
+        lines.push(row.join(","));
+    }
+    return lines;
+}
+
+// lists are joined with spaces, anything with a comma or quote in it gets quoted
+fn csv_cell(value: &Value) -> String {
+    let text = match value {
+        Value::Null => String::new(),
+        Value::String(text) => text.clone(),
+        Value::Array(items) => items.iter().map(csv_cell).collect::<Vec<String>>().join(" "),
+        other => other.to_string(),
+    };
+    if text.contains([',', '"', '\n']) {
+        return format!("\"{}\"", text.replace('"', "\"\""));
+    }
+    return text;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn csv_has_one_header_for_every_record() {
+        let records = vec![
+            json!({ "record": "turn", "puzzle": 1, "turn": 1, "guess": "crane" }),
+            json!({ "record": "turn", "puzzle": 1, "turn": 2, "guess": "tiger" }),
+            json!({ "record": "game", "puzzle": 1, "guesses": 2, "words": ["crane", "tiger"] }),
+        ];
+        let lines = csv_lines(&records);
+        assert_eq!(lines, vec![
+            "record,puzzle,turn,guess,guesses,words",
+            "turn,1,1,crane,,",
+            "turn,1,2,tiger,,",
+            "game,1,,,2,crane tiger",
+        ]);
+        assert_eq!(lines.iter().filter(|line| line.starts_with("record,")).count(), 1);
+        assert!(csv_lines(&[]).is_empty());
+    }
+}
@@ -0,0 +1,133 @@
+/*
+* words packed for the frequency heuristic, which looks at every allowed word every turn.
+* each letter the word lists use gets a number, in alphabetical order. a word is then a fixed array of letter
+* numbers and a bit mask of the letters in it, and letter counts are tables indexed by position and letter,
+* so nothing is split into strings or looked up by name while a guess is picked.
+*/
+
+use crate::alphabet;
+
+// longest word that can be packed, nerdle's equations are 8
+pub const MAX_WORD_LEN: usize = 16;
+// letters a mask can hold. a bigger alphabet shares the last number between the rest of its letters,
+// which only makes the heuristic a bit worse for those letters
+pub const MAX_LETTERS: usize = 64;
+
+// letter numbers, only the first word_len are used
+pub type Word = [u8; MAX_WORD_LEN];
+// a number for every letter in every position, indexed [position][letter]
+pub type LetterTable = [[usize; MAX_LETTERS]; MAX_WORD_LEN];
+// a bit per position, for where a letter is ruled out or known
+pub type Positions = [u16; MAX_LETTERS];
+
+// the letters used by the lists and their numbers
+pub struct Letters {
+    names: Vec<String>,
+}
+
+impl Letters {
+    pub fn new(words: &[&str]) -> Letters {
+        let mut names: Vec<String> = Vec::new();
+        for word in words.iter(){
+            for letter in alphabet::letters(word){
+                if let Err(position) = names.binary_search_by(|name| name.as_str().cmp(letter)) {
+                    names.insert(position, letter.to_string());
+                }
+            }
+        }
+        return Letters { names };
+    }
+
+    // None for a letter none of the words use
+    pub fn number(&self, letter: &str) -> Option<u8> {
+        return self.names.binary_search_by(|name| name.as_str().cmp(letter)).ok().map(|number| number.min(MAX_LETTERS - 1) as u8);
+    }
+
+    pub fn pack(&self, word: &str) -> Word {
+        let mut packed = [0; MAX_WORD_LEN];
+        for (i, letter) in alphabet::letters(word).into_iter().take(MAX_WORD_LEN).enumerate(){
+            packed[i] = self.number(letter).unwrap_or(0);
+        }
+        return packed;
+    }
+}
+
+// the bit for a letter in a mask
+pub fn bit(number: u8) -> u64 {
+    return 1 << number;
+}
+
+// a list of words packed, in the same order as words
+pub struct PackedWords<'a> {
+    pub words: Vec<&'a str>,
+    pub packed: Vec<Word>,
+    pub masks: Vec<u64>,
+    // words with no letter twice, the first guess has to be one
+    pub unique: Vec<bool>,
+    // for each position, the index of the next word that has different letters up to and including
+    // that position. in a sorted list that skips every word starting the same way
+    pub prefix_end: Vec<Vec<u32>>,
+    // how many words have each letter in each position, and every letter any of them use.
+    // the lists don't change during a game, so these are only counted once
+    pub counts: LetterTable,
+    pub present: u64,
+}
+
+impl<'a> PackedWords<'a> {
+    pub fn new(letters: &Letters, words: &[&'a str]) -> PackedWords<'a> {
+        let mut packed_words = PackedWords {
+            words: words.to_vec(),
+            packed: Vec::new(),
+            masks: Vec::new(),
+            unique: Vec::new(),
+            prefix_end: Vec::new(),
+            counts: [[0; MAX_LETTERS]; MAX_WORD_LEN],
+            present: 0,
+        };
+        for word in words.iter(){
+            let packed = letters.pack(word);
+            let word_len = alphabet::letter_count(word).min(MAX_WORD_LEN);
+            let mask = packed[..word_len].iter().fold(0, |mask, number| mask | bit(*number));
+            for (i, number) in packed[..word_len].iter().enumerate(){
+                packed_words.counts[i][*number as usize] += 1;
+            }
+            packed_words.present |= mask;
+            packed_words.packed.push(packed);
+            packed_words.masks.push(mask);
+            packed_words.unique.push(mask.count_ones() as usize == word_len);
+        }
+        let word_len = words.first().map(|word| alphabet::letter_count(word).min(MAX_WORD_LEN)).unwrap_or(0);
+        for i in 0..word_len{
+            // worked out from the end, a word's run ends where the next word's does unless the next word starts differently
+            let mut ends = vec![words.len() as u32; words.len()];
+            for index in (0..words.len().saturating_sub(1)).rev(){
+                let same_start = packed_words.packed[index][..=i] == packed_words.packed[index + 1][..=i];
+                ends[index] = if same_start { ends[index + 1] } else { index as u32 + 1 };
+            }
+            packed_words.prefix_end.push(ends);
+        }
+        return packed_words;
+    }
+}
+
+// both lists of a game, numbered the same way
+pub struct Packed<'a> {
+    pub letters: Letters,
+    pub allowed: PackedWords<'a>,
+    pub possible: PackedWords<'a>,
+    pub word_len: usize,
+}
+
+impl<'a> Packed<'a> {
+    pub fn new(allowed: &[&'a str], possible: &[&'a str]) -> Packed<'a> {
+        let every_word: Vec<&str> = allowed.iter().chain(possible.iter()).copied().collect();
+        let letters = Letters::new(&every_word);
+        let word_len = every_word.first().map(|word| alphabet::letter_count(word).min(MAX_WORD_LEN)).unwrap_or(0);
+        return Packed {
+            allowed: PackedWords::new(&letters, allowed),
+            possible: PackedWords::new(&letters, possible),
+            letters,
+            word_len,
+        };
+    }
+}
@@ -2,108 +2,154 @@
 * guesses words for wordle. depends on main. methods are explained in blog post
 */
 
-use std::io;
+use std::cmp::Reverse;
+use std::collections::BTreeSet;
 use std::collections::HashMap;
 use std::collections::HashSet;
 
-// get a collection of letters that the guess word should use.
-pub fn suggest_letters(words: &HashSet<&str>, loop_counter: &usize) -> Vec<char>{
-    let mut letters_freq: HashMap<char,usize> = HashMap::new();
-    
-    // get the letter frequencies
-    for word in words.into_iter(){
-        let letters: Vec<char> = word.chars().collect();
-        for i in 0..letters.len(){
-            let letter = letters[i];
-            let letter_freq_entry = letters_freq.entry(letter).or_insert(0);
-            *letter_freq_entry += 1;
-        }
+use crate::alphabet;
+use crate::game;
+use crate::game::Pools;
+use crate::game::Feedback;
+use crate::packed;
+use crate::packed::{Letters, LetterTable, PackedWords, Positions, MAX_LETTERS, MAX_WORD_LEN};
+
+// omit list positions for a letter that isn't in the word at all
+const EVERY_POSITION: u16 = u16::MAX;
+
+// how the solver picks its next guess
+#[derive(Clone, Copy)]
+pub enum Strategy {
+    // the letter position heuristic from the blog post
+    Frequency,
+    // the guess that splits the candidates up the most, measured in bits
+    Entropy,
+    // the guess that leaves the fewest candidates on average
+    ExpectedSize,
+    // the guess that leaves the fewest candidates in the worst case, for hosts that pick the worst case like absurdle
+    Minimax,
+}
+
+impl Strategy {
+    pub fn parse(name: &str) -> Result<Strategy, String> {
+        return match name {
+            "frequency" => Ok(Strategy::Frequency),
+            "entropy" => Ok(Strategy::Entropy),
+            "expected" => Ok(Strategy::ExpectedSize),
+            "minimax" => Ok(Strategy::Minimax),
+            _ => Err(format!("Unknown strategy '{}'. Use frequency, entropy, expected or minimax", name)),
+        };
+    }
+
+    // what parse reads back
+    pub fn name(&self) -> &'static str {
+        return match self {
+            Strategy::Frequency => "frequency",
+            Strategy::Entropy => "entropy",
+            Strategy::ExpectedSize => "expected",
+            Strategy::Minimax => "minimax",
+        };
     }
 
-    // sort that row by smallest to largest
-    let mut sorted_row: Vec<(char,usize)> = letters_freq.into_iter().collect();
-    sorted_row.sort_by_key(|a| a.1);
-    sorted_row.reverse();
+    // the frequency strategy works from where each letter goes, which only the wordle colors say
+    pub fn check_feedback(&self, feedback: Feedback) -> Result<(), String> {
+        return match (self, feedback) {
+            (Strategy::Frequency, Feedback::CommonLetters | Feedback::Pegs) => Err("The frequency strategy needs wordle colors, use entropy, expected or minimax".to_string()),
+            _ => Ok(()),
+        };
+    }
+}
+
+// get a collection of letters that the guess word should use, as letter numbers.
+pub fn suggest_letters(words: &PackedWords, word_len: usize, loop_counter: usize) -> Vec<u8>{
+    // get the letter frequencies, every position together
+    let mut letters_freq = [0usize; MAX_LETTERS];
+    for row in words.counts[..word_len].iter(){
+        for number in 0..MAX_LETTERS{
+            letters_freq[number] += row[number];
+        }
+    }
 
-    // take 5 letters according to the loop count.
-    let suggest_letters = vec![sorted_row[0+(loop_counter*5)].0,
-                           sorted_row[1+(loop_counter*5)].0,
-                           sorted_row[2+(loop_counter*5)].0,
-                           sorted_row[3+(loop_counter*5)].0,
-                           sorted_row[4+(loop_counter*5)].0];
+    // sort the letters the words use by most to least common. the sort is stable and the
+    // letter numbers are alphabetical, so letters that are just as common go alphabetically
+    let mut sorted_row: Vec<u8> = (0..MAX_LETTERS as u8).filter(|number| letters_freq[*number as usize] > 0).collect();
+    sorted_row.sort_by_key(|number| Reverse(letters_freq[*number as usize]));
+
+    // take a word's worth of letters according to the loop count.
+    // small alphabets like nerdle's symbols can run out, then there's just fewer
+    return sorted_row.into_iter()
+        .skip(loop_counter*word_len)
+        .take(word_len)
+        .collect();
+}
 
-    return suggest_letters;
+// the frequencies of each letter in each position, and which letters the words use at all
+pub struct LetterFrequencies {
+    pub counts: LetterTable,
+    pub present: u64,
+    pub word_len: usize,
 }
 
 // get the frequencies of each letter in their positions
-// use the omit list and include list to force letters in or out of their positions
-pub fn get_letter_frequencies(words: &HashSet<&str>, board_state: &HashMap<String,Vec<u8>>) -> HashMap<char,Vec<usize>>{
+// use the omit list and include list to force letters in or out of their positions.
+// only letters that show up in a position are forced, the rest stay at 0
+pub fn get_letter_frequencies(letters: &Letters, words: &PackedWords, word_len: usize, board_state: &HashMap<String,Vec<u8>>) -> LetterFrequencies{
     // omit list, used to indicate letters that are definitely not in the set and in the wrong position
-    let omit_list = build_omit_list(board_state);
+    let omit_list = build_omit_list(letters, board_state);
     // include list, used to indicate letters that are definitely in the right position.
-    let include_list = build_include_list(board_state);
-
-    let mut letter_dist: HashMap<char,Vec<usize>> = HashMap::new();
-    // for each word in our word list
-    for word in words.into_iter(){ 
-        let letters: Vec<char> = word.chars().collect();
-        // i is the position we're analyzing in a word
-        for i in 0..letters.len(){
-            let letter = letters[i];
+    let include_list = build_include_list(letters, board_state);
+
+    // start from the counts for the whole list
+    let mut letter_dist = LetterFrequencies { counts: words.counts, present: words.present, word_len };
+    // i is the position we're analyzing
+    for i in 0..word_len{
+        for number in 0..MAX_LETTERS{
+            if letter_dist.counts[i][number] == 0 {
+                continue
+            }
             // omit first so that we dont mess up our include list, which is the more accurate guess.
-            // if a letter is in the omit list
-            if omit_list.contains_key(&letter) {
-                let omit_list_locs = match omit_list.get(&letter){
-                    Some(a) => a,
-                    None => panic!("Value is not in key like we just validated. Data corruption has occured.")
-                };
-                // if the position we're analyzing is in the omit vec for this letter
-                if omit_list_locs.contains(&i) || omit_list_locs.contains(&6) {
-                    let letter_l = letter_dist.entry(letter).or_insert(vec![0,0,0,0,0]);
-                    letter_l[i] = 0; // hard set the location to no occurences
-                    continue
-                }
+            // if the position we're analyzing is in the omit positions for this letter
+            if omit_list[number] & (1 << i) != 0 {
+                letter_dist.counts[i][number] = 0; // hard set the location to no occurences
             }
-            // if a letter is in the include list
-            if include_list.contains_key(&letter) {
-                let include_list_locs = match include_list.get(&letter){
-                    Some(a) => a,
-                    None => panic!("Value is not in key like we just validated. Data corruption has occured.")
-                };
-                // if the include position for that letter is the position we're analyzing
-                if include_list_locs.contains(&i){
-                    let letter_l = letter_dist.entry(letter).or_insert(vec![0,0,0,0,0]);
-                    letter_l[i] = 200000; // hard set the location super high so that the letter doesn't get rotated
-                    continue
-                }
+            // if the include position for that letter is the position we're analyzing
+            else if include_list[number] & (1 << i) != 0 {
+                letter_dist.counts[i][number] = 200000; // hard set the location super high so that the letter doesn't get rotated
             }
-            // if we haven't quit by this point then our letter and position isn't in the omit and include lists
-            // so just add the letter
-            let letter_l = letter_dist.entry(letter).or_insert(vec![0,0,0,0,0]);
-            letter_l[i] = letter_l[i] + 1 // increment the position in the existing vec for that letter
         }
     }
 
     return letter_dist;
 }
 
-// omit list. a letter and position tuple, where the position is where to omit the letter from.
-// a position >5 indicates an omit from every position
-fn build_omit_list(board_state: &HashMap<String,Vec<u8>>) -> HashMap<char,Vec<usize>> {
-    let mut omit_list: HashMap<char,Vec<usize>> = HashMap::new();
+// omit list. a bit for each position to omit the letter from, EVERY_POSITION omits it from every position.
+// letters none of the words use are left out, there's nothing to omit them from
+pub fn build_omit_list(letters: &Letters, board_state: &HashMap<String,Vec<u8>>) -> Positions {
+    let mut omit_list: Positions = [0; MAX_LETTERS];
     // for each play on the game board
     for (guess,result) in board_state.iter(){
-        let guess_split: Vec<char> = guess.chars().collect();
-        for i in 0..guess_split.len(){ // guess_split and result should be the same length
+        let guess_split: Vec<&str> = alphabet::letters(guess);
+        for i in 0..guess_split.len().min(MAX_WORD_LEN){ // guess_split and result should be the same length
+            let number = match letters.number(guess_split[i]) {
+                Some(number) => number as usize,
+                None => continue,
+            };
             // 0 indicates a guess letter is not in the string at all.
+            // unless the same guess has that letter as a 1 or 2 somewhere else, then there just isn't another copy.
+            // so only omit it from this position
             if result[i] == 0 {
-                omit_list.insert(guess_split[i],vec![6]);
-            } 
+                let other_copy = (0..guess_split.len()).any(|j| guess_split[j] == guess_split[i] && result[j] > 0);
+                if other_copy {
+                    omit_list[number] |= 1 << i;
+                }
+                else {
+                    omit_list[number] = EVERY_POSITION;
+                }
+            }
             // 1 indicates a guess letter is in the string, but not in the right position
             // so omit it from the specific position
             else if result[i] == 1 {
-                let omit_list_l = omit_list.entry(guess_split[i]).or_insert(vec![]);
-                omit_list_l.push(i)
+                omit_list[number] |= 1 << i;
             }
         }
     }
@@ -111,203 +157,423 @@ fn build_omit_list(board_state: &HashMap<String,Vec<u8>>) -> HashMap<char,Vec<us
     return omit_list;
 }
 
-// include list. a letter and a position tuple, where the position is where to put the letter.
+// include list. a bit for each position where the letter is known to be.
 // conceptually an inverse omit list, where all other letters are removed, and the freq is set really high.
-fn build_include_list(board_state: &HashMap<String,Vec<u8>>) -> HashMap<char,Vec<usize>> {
-    let mut include_list: HashMap<char,Vec<usize>> = HashMap::new();
+pub fn build_include_list(letters: &Letters, board_state: &HashMap<String,Vec<u8>>) -> Positions {
+    let mut include_list: Positions = [0; MAX_LETTERS];
     // for each play on the game board
     for (guess,result) in board_state.iter(){
-        let guess_split: Vec<char> = guess.chars().collect();
-        for i in 0..guess_split.len(){ // guess_split and result should be the same length
+        let guess_split: Vec<&str> = alphabet::letters(guess);
+        for i in 0..guess_split.len().min(MAX_WORD_LEN){ // guess_split and result should be the same length
             // 2 indicates a guess letter is in the guess location
             if result[i] == 2 {
-                let include_list_l = include_list.entry(guess_split[i]).or_insert(vec![]);
-                include_list_l.push(i)
-            } 
+                if let Some(number) = letters.number(guess_split[i]) {
+                    include_list[number as usize] |= 1 << i;
+                }
+            }
         }
     }
-    
+
     return include_list;
 }
 
-// a list of letters which are in the file but not in the correct position.
+// a mask of letters which are in the word but not in the correct position.
 // the omit list already takes care of making sure these letters are not in the wrong position
 // the include list takes care of letters in the correct position.
-// so this just needs to be a list of letters that were in the string but in the wrong spot.
-// position will work itself out from the omit list and include list
-fn build_required_list(board_state: &HashMap<String,Vec<u8>>) -> Vec<char> {
-    let mut required_letters: Vec<char> = Vec::new();
+// so this just needs to be the letters that were in the string but in the wrong spot.
+// position will work itself out from the omit list and include list.
+// a letter none of the words use can't be matched, so it fills the whole mask and no word has them all
+pub fn build_required_list(letters: &Letters, board_state: &HashMap<String,Vec<u8>>) -> u64 {
+    let mut required_letters: u64 = 0;
     // for each play on the game board
     for (guess,result) in board_state.iter(){
-        let guess_split: Vec<char> = guess.chars().collect();
+        let guess_split: Vec<&str> = alphabet::letters(guess);
         for i in 0..guess_split.len(){ // guess_split and result should be the same length
-            // 0 indicates a guess letter is not in the string at all.
+            // 1 indicates a guess letter is in the word somewhere else
             if result[i] == 1 {
-                required_letters.push(guess_split[i]); // include the letter
-            } 
+                required_letters |= letters.number(guess_split[i]).map(packed::bit).unwrap_or(u64::MAX); // include the letter
+            }
         }
     }
-    
+
     return required_letters
 }
 
-// returns a vector where each position is a distance list for that position in the string
-pub fn get_distance_list(letter_dist: &HashMap<char,Vec<usize>>) -> Vec<Vec<(char,usize)>>{
-    // iterate over hashmap pulling out the vec's values into separate hashmaps. push those to a vec to be our distance lists.
-    let mut distance_lists: Vec<Vec<(char,usize)>> = Vec::new();
-    for i in 0..5{
-        // unpack a hashmap of just distributions for 1 specific position
-        let mut ret_row: HashMap<char,usize> = HashMap::new();
-        for (letter,list) in letter_dist.iter(){
-            ret_row.insert(*letter,list[i]);
+// returns the distance of every letter in every position from the most common letter in that position.
+// a letter that never shows up in a position, or was omitted from it, is really far so it won't be rotated in.
+// letters the words don't use at all stay at 0
+pub fn get_distance_list(letter_dist: &LetterFrequencies) -> LetterTable{
+    let mut distance_lists: LetterTable = [[0; MAX_LETTERS]; MAX_WORD_LEN];
+    for (row, distance_list) in letter_dist.counts.iter().zip(distance_lists.iter_mut()).take(letter_dist.word_len){
+        let used = |number: &usize| letter_dist.present & packed::bit(*number as u8) != 0;
+        // all distance are with reference to the optimal
+        let optimal_freq = (0..MAX_LETTERS).filter(used).map(|number| row[number]).max().unwrap_or(0);
+        for number in (0..MAX_LETTERS).filter(used) {
+            let freq = row[number];
+            // if the frequency is 0 it should be skipped
+            distance_list[number] = if freq == 0 {
+                1000000 // something really high that wont be rotated.
+            } else {
+                optimal_freq - freq
+            };
         }
+    }
 
+    // return looks like distance_lists[position][letter]
+    return distance_lists;
+}
 
-        // sort that row by smallest to largest
-        let mut sorted_row: Vec<(char,usize)> = ret_row.into_iter().collect();
-        sorted_row.sort_by_key(|a| a.1);
-        sorted_row.reverse();
-        // artificial entry of an ending letter to not rotate past
-        sorted_row.push(('.',0));
+// take each word in words, assign a distance score to it according to the distance lists, return the lowest distance score word. this is the best guess
+// the word needs every required letter, and no letter twice when unique is set.
+// words with the same score go to the candidates first, since they might be the answer, then alphabetically
+pub fn suggest_word(words: &PackedWords, candidates: &[&str], distance_lists: &LetterTable, word_len: usize, required_letters: u64, unique: bool) -> String{
+    // only worked out when two words tie, which isn't often
+    let mut candidate_set: Option<HashSet<&str>> = None;
+    let mut is_candidate = |word: &str| candidate_set.get_or_insert_with(|| candidates.iter().copied().collect()).contains(word);
+
+    // the best word so far as its distance and index, and whether it's a candidate once that's been needed
+    let mut best: Option<(usize, usize)> = None;
+    let mut best_is_candidate: Option<bool> = None;
+    let mut index = 0;
+    'words: while index < words.packed.len(){
+        // make sure our word has all the required letters
+        // and that our first word does not have duplicate letters
+        if words.masks[index] & required_letters != required_letters || (unique && !words.unique[index]) {
+            index += 1;
+            continue
+        }
 
-        // iterate over frequency row and build a distance list
-        let mut distance_list: Vec<(char,usize)> = Vec::new();
-        // all distance are with reference to the optimal
-        if sorted_row.len() <= 0{
-            panic!("sorted_row size is 0, which is not possible.")
+        // accumulator will be the total distance for a word.
+        // distances only add up, so once the start of a word is further than the best word,
+        // every word that starts the same way is too and they can all be skipped
+        let limit = best.map(|(best_distance, _)| best_distance).unwrap_or(usize::MAX);
+        let word = &words.packed[index];
+        let mut accumulator = 0;
+        for i in 0..word_len{
+            accumulator += distance_lists[i][word[i] as usize];
+            if accumulator > limit {
+                index = words.prefix_end[i][index] as usize;
+                continue 'words;
+            }
         }
-        let (_optimal_letter,optimal_freq) = sorted_row[0];
-        for i in 0..sorted_row.len() {
-            let distance: usize;
-            let (letter,freq) = sorted_row[i];
-            // if this is the last letter or if the frequency is 0 indicating it should be skipped
-            if i == (sorted_row.len()-1) || freq == 0 {
-                distance = 1000000; // something really high that wont be rotated.
-            } else { 
-                let (_next_letter,_next_freq) = sorted_row[i+1]; // grab the next letters frequence, store it under this letter.
-                distance = optimal_freq - freq;
+
+        // words come in alphabetical order, so the first one stays on a full tie
+        match best {
+            Some((best_distance, _)) if accumulator > best_distance => {}
+            Some((best_distance, best_index)) if accumulator == best_distance => {
+                let best_candidate = *best_is_candidate.get_or_insert_with(|| is_candidate(words.words[best_index]));
+                if !best_candidate && is_candidate(words.words[index]) {
+                    best = Some((accumulator, index));
+                    best_is_candidate = Some(true);
+                }
+            }
+            _ => {
+                best = Some((accumulator, index));
+                best_is_candidate = None;
             }
-            distance_list.push((letter,distance));
         }
+        index += 1;
+    }
+
+    // an empty string if every word has been ruled out, let the caller decide what to do
+    return best.map(|(_, index)| words.words[index].to_string()).unwrap_or_default();
+}
 
-        distance_lists.push(distance_list);
+// run one turn of the solver and return the word it would guess.
+// the first two guesses use the most common letters, after that it's all letter positions.
+// letters are counted over the possible answers, but any allowed word can be the guess.
+// candidates only break ties between words that score the same.
+pub fn next_guess(pools: &Pools, candidates: &[&str], board_state: &HashMap<String,Vec<u8>>, _answers: &[&str], loop_counter: usize) -> String {
+    let packed = &pools.packed;
+    // get letter frequencies considering position
+    let letter_dist = get_letter_frequencies(&packed.letters, &packed.possible, packed.word_len, board_state);
+
+    // get letter frequencies without considering positions
+    let letters: Vec<u8> = if loop_counter >= 2{
+        vec![]
     }
+    else{
+        suggest_letters(&packed.possible, packed.word_len, loop_counter)
+    };
 
+    // get distance lists for each row
+    let distance_lists = get_distance_list(&letter_dist);
 
-    // return looks like distance_lists<distance_list<letter,distance>>
-    return distance_lists;
+    // if we are guessing based on simple letter frequencies, there will be letters to use.
+    // if we are guessing based on letter frequencies and positions, then we need to build a list of letters to include
+    let required_letters = build_required_list(&packed.letters, board_state);
+    let forced_letters = letters.iter().fold(0, |mask, number| mask | packed::bit(*number));
+    // the first word can't have a letter twice
+    let unique = board_state.is_empty();
+
+    // get a word with either required letters or not depending on the loop_counter
+    let guess_word = if letters.is_empty() {
+        suggest_word(&packed.allowed, candidates, &distance_lists, packed.word_len, required_letters, unique)
+    } else {
+        suggest_word(&packed.allowed, candidates, &distance_lists, packed.word_len, forced_letters, unique)
+    };
+    // no allowed word has all of the common letters, so fall back to letter positions alone
+    if guess_word.is_empty() && !letters.is_empty() {
+        return suggest_word(&packed.allowed, candidates, &distance_lists, packed.word_len, required_letters, unique);
+    }
+    return guess_word;
+}
+
+// pick the next guess with the given strategy.
+// candidates are the possible answers that still fit the board, only the scoring strategies look at them.
+pub fn best_guess(strategy: Strategy, pools: &Pools, candidates: &[&str], board_state: &HashMap<String,Vec<u8>>, answers: &[&str], loop_counter: usize) -> String {
+    return match strategy {
+        Strategy::Frequency => next_guess(pools, candidates, board_state, answers, loop_counter),
+        Strategy::Entropy => best_by_score(&pools.allowed, candidates, |guess_word| expected_information(pools, candidates, guess_word)),
+        Strategy::ExpectedSize => best_by_score(&pools.allowed, candidates, |guess_word| -expected_remaining(pools, candidates, guess_word)),
+        Strategy::Minimax => best_by_score(&pools.allowed, candidates, |guess_word| minimax_score(pools.feedback, candidates, guess_word)),
+    };
 }
 
-// take each word in words, assign a distance score to it according to the distance lists, check if its in answers, return the lowest distance score word. this is the best guess
-pub fn suggest_word(words: &HashSet<&str>, distance_lists: &Vec<Vec<(char,usize)>>, board_state:&HashMap<String,Vec<u8>>, answers: &Vec<&str>, letters: Vec<char>) -> String{
+// how good a guess is by the strategy's own measure, higher is better.
+// the frequency heuristic doesn't score single words, so it's measured in bits like entropy
+pub fn score(strategy: Strategy, pools: &Pools, candidates: &[&str], guess_word: &str) -> f64 {
+    return match strategy {
+        Strategy::Frequency | Strategy::Entropy => expected_information(pools, candidates, guess_word),
+        Strategy::ExpectedSize => -expected_remaining(pools, candidates, guess_word),
+        Strategy::Minimax => minimax_score(pools.feedback, candidates, guess_word),
+    };
+}
 
-    // hashmap to store each word and its distance value
-    let mut word_distances: HashMap<&str,usize> = HashMap::new();
+// the best few guesses and their scores, best first. candidates and then alphabetical order break ties
+pub fn top_guesses(strategy: Strategy, pools: &Pools, candidates: &[&str], count: usize) -> Vec<(String, f64)> {
+    let candidate_set: HashSet<&str> = candidates.iter().copied().collect();
+    let mut scored: Vec<(f64, bool, &str)> = pools.allowed.iter()
+        .map(|guess_word| (score(strategy, pools, candidates, guess_word), candidate_set.contains(guess_word), *guess_word))
+        .collect();
+    scored.sort_by(|a, b| b.0.total_cmp(&a.0).then(b.1.cmp(&a.1)).then(a.2.cmp(b.2)));
+    return scored.into_iter().take(count).map(|(score, _, guess_word)| (guess_word.to_string(), score)).collect();
+}
 
-    // for every word, split it into its characters, add the distance of each letter to the accumulator, store the word and its score.
-    for word in words.iter(){
-        let word_letters: Vec<char> = word.chars().collect();
-        
-        // accumulator will be the total distance for a word.
-        let mut accumulator = 0;
-        for i in 0..word_letters.len(){
-            let letter = word_letters[i];
-            // unpack the positions distance list;
-            let distance_list = &distance_lists[i];
-            // get the distance for the current letter
-            let mut distance = 0;
-            for distance_pair in distance_list.iter(){
-                if distance_pair.0 == letter{
-                    distance = distance_pair.1;
-                }
-            }
-            // add the distance to the accumulator
-            accumulator += distance;
+// fewer candidates left in the worst case is better. between guesses with the same worst case, the one that
+// splits the candidates into more groups wins, since there are fewer ways left for the host to dodge.
+// there can't be more groups than pattern codes (3^8 for nerdle) so the tie break never outweighs a whole candidate.
+pub fn minimax_score(feedback: Feedback, candidates: &[&str], guess_word: &str) -> f64 {
+    let sizes = game::bucket_sizes(feedback, candidates, guess_word);
+    let worst = sizes.iter().max().copied().unwrap_or(0);
+    let groups = sizes.iter().filter(|size| **size > 0).count();
+    let codes = feedback.code_count(alphabet::letter_count(guess_word));
+    return -(worst as f64) + groups as f64 / (codes + 1) as f64;
+}
+
+// the chance of getting each pattern code back for a guess, with every candidate weighted by the prior.
+// the codes depend on the pools' feedback, so this works the same for colors, jotto and mastermind
+pub fn pattern_chances(pools: &Pools, candidates: &[&str], guess_word: &str) -> Vec<f64> {
+    if pools.prior.is_uniform() {
+        let total = candidates.len() as f64;
+        return game::bucket_sizes(pools.feedback, candidates, guess_word).iter().map(|size| *size as f64 / total).collect();
+    }
+    let mut chances = vec![0.0; pools.feedback.code_count(alphabet::letter_count(guess_word))];
+    let mut total = 0.0;
+    for candidate in candidates.iter(){
+        let weight = pools.prior.weight(candidate);
+        chances[pools.feedback.code(candidate, guess_word) as usize] += weight;
+        total += weight;
+    }
+    if total > 0.0 {
+        chances.iter_mut().for_each(|chance| *chance /= total);
+    }
+    return chances;
+}
+
+// expected information from a guess in bits. more bits means the candidates get split into smaller groups.
+pub fn expected_information(pools: &Pools, candidates: &[&str], guess_word: &str) -> f64 {
+    let mut bits = 0.0;
+    for p in pattern_chances(pools, candidates, guess_word){
+        if p > 0.0 {
+            bits -= p * p.log2();
         }
+    }
+    return bits;
+}
 
-        // assign the word and distances to the hashmap
-        word_distances.insert(word,accumulator);
+// average number of candidates left after a guess. guessing the answer leaves none.
+pub fn expected_remaining(pools: &Pools, candidates: &[&str], guess_word: &str) -> f64 {
+    let sizes = game::bucket_sizes(pools.feedback, candidates, guess_word);
+    let chances = pattern_chances(pools, candidates, guess_word);
+    let mut remaining = 0.0;
+    for (size, chance) in sizes.iter().zip(chances.iter()){
+        remaining += *size as f64 * chance;
+    }
+    // the solved bucket only holds the guess itself
+    if candidates.contains(&guess_word) {
+        remaining -= chances[pools.feedback.code(guess_word, guess_word) as usize];
     }
-    word_distances.remove("");
+    return remaining;
+}
 
-    // if we are guessing based on simple letter frequencies, there will be a letters vec.
-    // if we are guessing based on letter frequencies and positions, then we need to build a list of letters to include
-    let mut required_letters: Vec<char> = Vec::new();
-    if letters.len() > 0{
-        required_letters = letters;
-//        required_letters = build_required_list(board_state);
-    }
-    else {
-        required_letters = build_required_list(board_state);
-    }
-    loop {
-        let guess = match word_distances.iter().min_by_key(|entry| entry.1){
-            Some(a) => a,
-            None => panic!("No guess was found in get_word")
+// score every word and return the highest. candidates win ties since they might be the answer,
+// then the words come in alphabetical order and the first one stays.
+fn best_by_score<F: Fn(&str) -> f64>(words: &BTreeSet<&str>, candidates: &[&str], score: F) -> String {
+    // with one or two left the best we can do is guess one of them
+    if candidates.len() <= 2 {
+        return candidates.first().map(|word| word.to_string()).unwrap_or_default();
+    }
+    let candidate_set: HashSet<&str> = candidates.iter().copied().collect();
+    let mut best: Option<(f64, bool, &str)> = None;
+    for word in words.iter(){
+        let entry = (score(word), candidate_set.contains(word), *word);
+        best = match best {
+            Some(current) if (current.0, current.1) >= (entry.0, entry.1) => Some(current),
+            _ => Some(entry),
         };
+    }
+    return best.map(|(_, _, word)| word.to_string()).unwrap_or_default();
+}
 
-        let guess_word = guess.0.to_string();
-        let guess_word_vec: Vec<char> = guess_word.chars().collect();
+// parse a played guess and its results from a line like 'crane 00120'
+pub fn parse_history_line(line: &str) -> Result<(String,Vec<u8>), String> {
+    let parts: Vec<&str> = line.split_whitespace().collect();
+    if parts.len() != 2 {
+        return Err(format!("Could not read '{}'. make sure you're doing it like 'crane 00120'", line));
+    }
+    let guess_word = alphabet::normalize(parts[0]);
+    let state_vec = game::parse_colors(parts[1])?;
+    if state_vec.len() != alphabet::letter_count(&guess_word) {
+        return Err(format!("'{}' and '{}' are different lengths", guess_word, parts[1]));
+    }
+    return Ok((guess_word, state_vec));
+}
 
-        let mut valid_guess: bool = true;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packed::Packed;
 
-        // make sure our word has all the required letters
-        for required_letter in &required_letters{
-            if !guess_word_vec.contains(required_letter){
-                valid_guess = false;
-            }
-        }
+    const WORDS: [&str; 6] = ["crane", "eerie", "geese", "tiger", "tiler", "timer"];
 
-        // make sure our first word does not have duplicate letters        
-        if board_state.len() <= 0 {
-            for letter in guess_word_vec.iter(){
-                let mut guess_word_vec_clone = guess_word_vec.clone();
-                let index = guess_word_vec_clone.iter().position(|x| x == letter).unwrap();
-                guess_word_vec_clone.remove(index);
-                if guess_word_vec_clone.contains(&letter){
-                    valid_guess = false;
-                }
-            }
-        }
-        
-        if valid_guess {
-            return guess_word;
-        }
-        else { 
-            word_distances.remove(guess_word.as_str());
-        }
+    fn board(plays: &[(&str, [u8; 5])]) -> HashMap<String,Vec<u8>> {
+        return plays.iter().map(|(guess_word, results)| (guess_word.to_string(), results.to_vec())).collect();
     }
-}
 
-// get board results from user
-pub fn get_board_results() -> Vec<u8> {
-    // get input
-    let mut input: String = String::new();
-    io::stdin().read_line(&mut input).expect("Failed to read input");
+    #[test]
+    fn omit_list() {
+        let packed = Packed::new(&WORDS, &WORDS);
+        let number = |letter: &str| packed.letters.number(letter).unwrap() as usize;
+        let omit_list = build_omit_list(&packed.letters, &board(&[("crane", [0, 1, 0, 0, 2])]));
+        assert_eq!(omit_list[number("c")], EVERY_POSITION);
+        assert_eq!(omit_list[number("r")], 1 << 1);
+        assert_eq!(omit_list[number("a")], EVERY_POSITION);
+        assert_eq!(omit_list[number("e")], 0);
+    }
 
-    // trim the newline
-    if input.ends_with('\n') {
-        input.pop();
+    #[test]
+    fn omit_list_keeps_repeated_letters_that_were_colored() {
+        let packed = Packed::new(&WORDS, &WORDS);
+        let number = |letter: &str| packed.letters.number(letter).unwrap() as usize;
+        // eerie against crane. the last e is green so the gray e's only rule out their own positions
+        let omit_list = build_omit_list(&packed.letters, &board(&[("eerie", [0, 0, 1, 0, 2])]));
+        assert_eq!(omit_list[number("e")], 0b11);
+        assert_eq!(omit_list[number("r")], 1 << 2);
+        assert_eq!(omit_list[number("i")], EVERY_POSITION);
     }
 
-    // complain if the string isn't 5 characters long
-    if input.len() != 5{
-        panic!("Input is either too long or too short")
+    #[test]
+    fn include_list() {
+        let packed = Packed::new(&WORDS, &WORDS);
+        let number = |letter: &str| packed.letters.number(letter).unwrap() as usize;
+        let include_list = build_include_list(&packed.letters, &board(&[("crane", [0, 1, 0, 0, 2]), ("tiger", [0, 0, 0, 2, 0])]));
+        assert_eq!(include_list[number("e")], 1 << 4 | 1 << 3);
+        assert_eq!(include_list[number("r")], 0);
+        assert_eq!(include_list.iter().filter(|positions| **positions != 0).count(), 1);
     }
 
-    // parse the input into an array of integers
-    let split_input_raw: Vec<&str> = input.split("").collect();
-    let mut state_vec: Vec<u8> = Vec::new();
-    for entry in split_input_raw.iter(){
-        if entry.len() > 0{
-            // parse string like "0", "1", or "2" and handle errors
-            let state_entry = match entry.parse::<u8>() {
-                Ok(a) => a,
-                Err(_) => panic!("Could not parse input number. make sure you're doing it like '00120'")
-            };
-            state_vec.push(state_entry);
-        }
+    #[test]
+    fn required_list() {
+        let packed = Packed::new(&WORDS, &WORDS);
+        let bit = |letter: &str| packed::bit(packed.letters.number(letter).unwrap());
+        assert_eq!(build_required_list(&packed.letters, &HashMap::new()), 0);
+        assert_eq!(build_required_list(&packed.letters, &board(&[("crane", [0, 1, 0, 0, 2]), ("tiger", [1, 1, 0, 0, 0])])), bit("r") | bit("t") | bit("i"));
+        // no word has a z, so nothing can have every required letter
+        assert_eq!(build_required_list(&packed.letters, &board(&[("zebra", [1, 0, 0, 0, 0])])), u64::MAX);
     }
-    return state_vec
-}
 
+    #[test]
+    fn distance_list() {
+        let words = ["tiger", "tiler", "timer"];
+        let packed = Packed::new(&words, &words);
+        let number = |letter: &str| packed.letters.number(letter).unwrap() as usize;
+        let letter_dist = get_letter_frequencies(&packed.letters, &packed.possible, 5, &HashMap::new());
+        let distance_lists = get_distance_list(&letter_dist);
+        // every word starts with t, so t is the best first letter and anything else is never rotated in
+        assert_eq!(distance_lists[0][number("t")], 0);
+        assert_eq!(distance_lists[0][number("i")], 1000000);
+        // g, l and m are just as common in the middle
+        assert_eq!(distance_lists[2][number("g")], 0);
+        assert_eq!(distance_lists[2][number("m")], 0);
+        assert_eq!(distance_lists[2][number("t")], 1000000);
+        // nothing past the end of the words
+        assert!(distance_lists[5].iter().all(|distance| *distance == 0));
+    }
+
+    #[test]
+    fn distance_list_follows_the_board() {
+        let words = ["tiger", "tiler", "timer", "toner"];
+        let packed = Packed::new(&words, &words);
+        let number = |letter: &str| packed.letters.number(letter).unwrap() as usize;
+        // g is gone, and the i is known to be second
+        let board_state = board(&[("tiger", [2, 2, 0, 2, 2])]);
+        let letter_dist = get_letter_frequencies(&packed.letters, &packed.possible, 5, &board_state);
+        assert_eq!(letter_dist.counts[1][number("i")], 200000);
+        assert_eq!(letter_dist.counts[2][number("g")], 0);
+        let distance_lists = get_distance_list(&letter_dist);
+        assert_eq!(distance_lists[1][number("i")], 0);
+        assert_eq!(distance_lists[1][number("o")], 200000 - 1);
+        assert_eq!(distance_lists[2][number("g")], 1000000);
+        assert_eq!(distance_lists[2][number("l")], 0);
+    }
+
+    #[test]
+    fn suggest_word_picks_the_closest_word() {
+        let packed = Packed::new(&WORDS, &WORDS);
+        let number = |letter: &str| packed.letters.number(letter).unwrap() as usize;
+        let mut distance_lists: LetterTable = [[0; MAX_LETTERS]; MAX_WORD_LEN];
+        // every word but timer is pushed away by its first or third letter
+        distance_lists[0][number("c")] = 5;
+        distance_lists[0][number("e")] = 5;
+        distance_lists[0][number("g")] = 5;
+        distance_lists[2][number("g")] = 5;
+        distance_lists[2][number("l")] = 5;
+        assert_eq!(suggest_word(&packed.allowed, &[], &distance_lists, 5, 0, false), "timer");
+    }
+
+    #[test]
+    fn suggest_word_needs_the_required_letters() {
+        let packed = Packed::new(&WORDS, &WORDS);
+        let bit = |letter: &str| packed::bit(packed.letters.number(letter).unwrap());
+        let distance_lists: LetterTable = [[0; MAX_LETTERS]; MAX_WORD_LEN];
+        assert_eq!(suggest_word(&packed.allowed, &[], &distance_lists, 5, bit("m"), false), "timer");
+        assert_eq!(suggest_word(&packed.allowed, &[], &distance_lists, 5, bit("g") | bit("s"), false), "geese");
+        // geese and eerie repeat letters, so they can't be the first guess
+        assert_eq!(suggest_word(&packed.allowed, &[], &distance_lists, 5, bit("e"), true), "crane");
+        assert_eq!(suggest_word(&packed.allowed, &[], &distance_lists, 5, bit("g") | bit("s"), true), "");
+        assert_eq!(suggest_word(&packed.allowed, &[], &distance_lists, 5, u64::MAX, false), "");
+    }
+
+    #[test]
+    fn minimax_tie_break_never_outweighs_a_candidate() {
+        // nerdle has far more than a thousand patterns, so a guess can split the equations into that many groups
+        let equations = crate::nerdle::generate();
+        let candidates: Vec<&str> = equations.iter().map(|equation| equation.as_str()).collect();
+        let sizes = game::bucket_sizes(Feedback::Colors, &candidates, "48-32=16");
+        let worst = sizes.iter().max().copied().unwrap() as f64;
+        assert!(sizes.iter().filter(|size| **size > 0).count() > 1000);
+        let score = minimax_score(Feedback::Colors, &candidates, "48-32=16");
+        assert!(score > -worst && score < -worst + 1.0, "{} against a worst case of {}", score, worst);
+    }
+
+    #[test]
+    fn suggest_word_breaks_ties_the_same_way() {
+        let words = ["tiger", "tiler", "timer"];
+        let packed = Packed::new(&words, &words);
+        // no distances at all, so every word scores the same
+        let distance_lists: LetterTable = [[0; MAX_LETTERS]; MAX_WORD_LEN];
+        assert_eq!(suggest_word(&packed.allowed, &[], &distance_lists, 5, 0, true), "tiger");
+        assert_eq!(suggest_word(&packed.allowed, &["timer", "tiler"], &distance_lists, 5, 0, true), "tiler");
+    }
+}
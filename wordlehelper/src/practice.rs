@@ -0,0 +1,92 @@
+/*
+* practice mode. picks an answer, lets a human guess against it like the real game,
+* then grades the game to show what the solver would have guessed at each step.
+*/
+
+use std::io;
+use std::collections::BTreeSet;
+
+use serde_json::json;
+
+use crate::alphabet;
+use crate::analysis;
+use crate::cli::Game;
+use crate::game;
+use crate::game::Pools;
+use crate::game::MAX_GUESSES;
+use crate::output;
+use crate::player::Strategy;
+use crate::render;
+use crate::rng::Rng;
+use crate::share;
+
+pub fn practice(game: Game, pools: &Pools, answers: &[&str], seed: u64, strategy: Strategy) -> Result<(), String> {
+    if answers.is_empty() {
+        return Err("no answers to practice with".to_string());
+    }
+    let mut rng = Rng::new(seed);
+    let day = rng.below(answers.len());
+    let answer = answers[day];
+    output::say(&format!("practice game (seed {}). you have {} guesses.", seed, MAX_GUESSES));
+
+    // history keeps the guesses in the order they were played
+    let mut history: Vec<(String,Vec<u8>)> = Vec::new();
+    let mut candidates: Vec<&str> = pools.possible.clone();
+    while history.len() < MAX_GUESSES {
+        let guess_word = match read_guess(&pools.allowed) {
+            Some(guess_word) => guess_word,
+            None => break,
+        };
+
+        let state_vec = game::determine_board_results(answer, &guess_word);
+        let remaining = game::filter_candidates(&candidates, &guess_word, &state_vec);
+        output::turn(pools, json!({ "seed": seed }), history.len() + 1, &candidates, &guess_word, &state_vec, remaining.len());
+        candidates = remaining;
+        history.push((guess_word.clone(), state_vec.clone()));
+        if output::is_text() {
+            println!("{}", render::tiles(&guess_word, &state_vec));
+            println!("{}", render::keyboard(&history));
+        }
+
+        if game::is_solved(&state_vec) {
+            output::say(&format!("solved in {}/{}.", history.len(), MAX_GUESSES));
+            break
+        }
+    }
+    if history.len() == MAX_GUESSES && !game::is_solved(&history[MAX_GUESSES - 1].1) {
+        output::say("out of guesses.");
+    }
+    let solved = history.last().is_some_and(|(_, state_vec)| game::is_solved(state_vec));
+    let words: Vec<&str> = history.iter().map(|(guess_word, _)| guess_word.as_str()).collect();
+    output::record("game", json!({ "seed": seed, "puzzle": day, "answer": answer, "solved": solved, "guesses": history.len(), "words": words }));
+    if output::is_text() {
+        println!("the answer was '{}' (puzzle {}).", answer, day);
+        println!("{}", share::emoji_grid(game, day, &history));
+    }
+
+    analysis::print_report(&analysis::grade_game(strategy, pools, &answers[..day], &history));
+    return Ok(());
+}
+
+// ask for guesses until we get a word from the list. None when input runs out.
+pub fn read_guess(words: &BTreeSet<&str>) -> Option<String> {
+    loop {
+        let mut input = String::new();
+        match io::stdin().read_line(&mut input) {
+            Ok(0) | Err(_) => return None,
+            Ok(_) => {}
+        }
+        let guess_word = alphabet::normalize(input.trim());
+        // every word in the list is the same length, nerdle's equations are longer than words
+        let word_len = words.iter().next().map(|word| alphabet::letter_count(word)).unwrap_or(0);
+        if alphabet::letter_count(&guess_word) != word_len {
+            output::say(&format!("guesses need to be {} long.", word_len));
+        }
+        else if !words.contains(guess_word.as_str()) {
+            output::say(&format!("'{}' is not in the word list.", guess_word));
+        }
+        else {
+            return Some(guess_word);
+        }
+    }
+}
@@ -0,0 +1,125 @@
+/*
+* how likely each word is to be the answer. without a prior every candidate counts the same.
+* with a word frequency file (lines like 'crane,1234') words are ranked by count and the rank is
+* pushed through a sigmoid, so common words are close to 1 and obscure ones fall off towards 0.
+*/
+
+use std::collections::HashMap;
+use std::fs;
+
+use crate::wordlist;
+
+// the rank where a word is a coin flip, and how quickly the sigmoid falls off around it.
+// roughly the first few thousand common words end up likely, about the size of a real answer list
+const CENTER_RANK: f64 = 3000.0;
+const WIDTH: f64 = 400.0;
+// words the file doesn't have rank after every word it does, and never better than this.
+// otherwise a short file would leave them nearly as likely as its own words
+const UNSEEN_RANK: f64 = CENTER_RANK + 4.0 * WIDTH;
+
+pub struct Prior {
+    // empty means uniform
+    weights: HashMap<String, f64>,
+    // weight for words the file didn't mention
+    missing: f64,
+}
+
+impl Prior {
+    pub fn uniform() -> Prior {
+        return Prior { weights: HashMap::new(), missing: 1.0 };
+    }
+
+    pub fn is_uniform(&self) -> bool {
+        return self.weights.is_empty();
+    }
+
+    pub fn weight(&self, word: &str) -> f64 {
+        if self.weights.is_empty() {
+            return 1.0;
+        }
+        return *self.weights.get(word).unwrap_or(&self.missing);
+    }
+
+    pub fn load(path: &str) -> Result<Prior, String> {
+        let text = fs::read_to_string(path).map_err(|error| format!("Could not open prior file '{}': {}", path, error))?;
+        let prior = Prior::parse(&text);
+        if prior.weights.is_empty() {
+            return Err(format!("No 'word,count' lines were found in '{}'", path));
+        }
+        return Ok(prior);
+    }
+
+    // lines that don't have a word and a count (headers, comments) are skipped
+    pub fn parse(text: &str) -> Prior {
+        let mut counts: Vec<(String, f64)> = Vec::new();
+        for line in text.lines() {
+            let mut parts = line.split(',');
+            let (word, count) = match (parts.next(), parts.next()) {
+                (Some(word), Some(count)) => (wordlist::clean(word), count.trim()),
+                _ => continue,
+            };
+            if let Ok(count) = count.parse::<f64>() {
+                counts.push((word, count));
+            }
+        }
+        // most common first. equal counts keep file order
+        counts.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+        let mut weights: HashMap<String, f64> = HashMap::new();
+        for (rank, (word, _)) in counts.iter().enumerate() {
+            weights.entry(word.clone()).or_insert(sigmoid(rank as f64));
+        }
+        let missing = sigmoid((counts.len() as f64).max(UNSEEN_RANK));
+        return Prior { weights, missing };
+    }
+}
+
+fn sigmoid(rank: f64) -> f64 {
+    return 1.0 / (1.0 + ((rank - CENTER_RANK) / WIDTH).exp());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_counts() {
+        // a header, windows line endings, a decomposed accent and a line without a count
+        let prior = Prior::parse("word,count\r\nCrane,10\r\nsi\u{301}la,500\r\nslate\r\nadieu, 20 \r\n");
+        assert_eq!(prior.weights.len(), 3);
+        assert!(prior.weights.contains_key("crane"));
+        assert!(prior.weights.contains_key("síla"));
+        assert!(prior.weights.contains_key("adieu"));
+        assert!(!prior.weights.contains_key("slate"));
+        assert!(Prior::parse("# nothing here\n").is_uniform());
+    }
+
+    #[test]
+    fn common_words_weigh_more() {
+        let prior = Prior::parse("crane,10\nsíla,500\nadieu,20\n");
+        assert!(prior.weight("síla") > prior.weight("adieu"));
+        assert!(prior.weight("adieu") > prior.weight("crane"));
+        assert_eq!(prior.weight("síla"), sigmoid(0.0));
+    }
+
+    #[test]
+    fn unseen_words_rank_last() {
+        // a short file still sets its words well apart from the ones it doesn't have
+        let prior = Prior::parse("crane,10\nadieu,20\n");
+        assert!(prior.weight("slate") < prior.weight("crane"));
+        assert!(prior.weight("slate") < 0.1);
+        assert!(prior.weight("crane") > 0.9);
+        // a long file ranks them after its last word
+        let text: String = (0..5000).map(|i| format!("w{},{}\n", i, 5000 - i)).collect();
+        let prior = Prior::parse(&text);
+        assert!(prior.weight("slate") < prior.weight("w4999"));
+    }
+
+    #[test]
+    fn uniform_weighs_everything_the_same() {
+        let prior = Prior::uniform();
+        assert!(prior.is_uniform());
+        assert_eq!(prior.weight("crane"), 1.0);
+        assert_eq!(prior.weight("slate"), 1.0);
+    }
+}
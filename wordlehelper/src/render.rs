@@ -0,0 +1,140 @@
+/*
+* draws guesses and their results for the terminal.
+* colors are only used when stdout is a terminal, piped output gets plain text instead.
+*/
+
+use std::io::IsTerminal;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::alphabet;
+use crate::game::Feedback;
+
+const GREEN: &str = "\x1b[1;30;42m";
+const YELLOW: &str = "\x1b[1;30;43m";
+const GRAY: &str = "\x1b[1;37;100m";
+const RESET: &str = "\x1b[0m";
+
+pub const KEYBOARD_ROWS: [&str; 3] = ["qwertyuiop", "asdfghjkl", "zxcvbnm"];
+
+static COLOR: AtomicBool = AtomicBool::new(false);
+
+// how the user wants colors, from --color
+pub enum ColorChoice {
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorChoice {
+    pub fn parse(value: &str) -> Result<ColorChoice, String> {
+        return match value {
+            "auto" => Ok(ColorChoice::Auto),
+            "always" => Ok(ColorChoice::Always),
+            "never" => Ok(ColorChoice::Never),
+            _ => Err(format!("Unknown color choice '{}'. Use auto, always or never", value)),
+        };
+    }
+}
+
+// decide once at startup if we're drawing in color
+pub fn init(choice: &ColorChoice) {
+    let color = match choice {
+        ColorChoice::Auto => std::io::stdout().is_terminal(),
+        ColorChoice::Always => true,
+        ColorChoice::Never => false,
+    };
+    COLOR.store(color, Ordering::Relaxed);
+}
+
+fn color_enabled() -> bool {
+    return COLOR.load(Ordering::Relaxed);
+}
+
+fn paint(text: &str, state: u8) -> String {
+    let color = match state {
+        2 => GREEN,
+        1 => YELLOW,
+        _ => GRAY,
+    };
+    return format!("{}{}{}", color, text, RESET);
+}
+
+// a guess as a row of colored tiles, one per letter.
+// without color it's the guess and its results like 'CRANE 00120'
+pub fn tiles(guess: &str, state_vec: &[u8]) -> String {
+    if !color_enabled() {
+        let states: String = state_vec.iter().map(|state| state.to_string()).collect();
+        return format!("{} {}", guess.to_uppercase(), states);
+    }
+    let mut row = String::new();
+    for (letter, state) in alphabet::letters(guess).iter().zip(state_vec.iter()) {
+        row.push_str(&paint(&format!(" {} ", letter.to_uppercase()), *state));
+    }
+    return row;
+}
+
+// a guess and what the game said about it. colors get tiles, anything else is written out like 'CRANE 2 black 1 white'
+pub fn result(feedback: Feedback, guess: &str, results: &[u8]) -> String {
+    if let Feedback::Colors = feedback {
+        return tiles(guess, results);
+    }
+    return format!("{} {}", guess.to_uppercase(), feedback.describe(results));
+}
+
+// every guess so far, one row each
+pub fn board(feedback: Feedback, history: &[(String,Vec<u8>)]) -> String {
+    let rows: Vec<String> = history.iter().map(|(guess, results)| result(feedback, guess, results)).collect();
+    return rows.join("\n");
+}
+
+// the best thing we know about every letter that's been played. 2 beats 1 beats 0.
+pub fn letter_states(history: &[(String,Vec<u8>)]) -> Vec<(&str,u8)> {
+    let mut states: Vec<(&str,u8)> = Vec::new();
+    for (guess, state_vec) in history.iter() {
+        for (letter, state) in alphabet::letters(guess).into_iter().zip(state_vec.iter()) {
+            match states.iter_mut().find(|(known, _)| *known == letter) {
+                Some(entry) => entry.1 = entry.1.max(*state),
+                None => states.push((letter, *state)),
+            }
+        }
+    }
+    return states;
+}
+
+// an on screen keyboard colored by what we know about each letter.
+// without color it lists the letters by what we know instead
+pub fn keyboard(history: &[(String,Vec<u8>)]) -> String {
+    let states = letter_states(history);
+    if !color_enabled() {
+        let mut lines: Vec<String> = Vec::new();
+        for (state, label) in [(2, "in place"), (1, "in word"), (0, "not in word")] {
+            let letters: String = states.iter().filter(|(_, known)| *known == state).map(|(letter, _)| letter.to_uppercase()).collect();
+            if !letters.is_empty() {
+                lines.push(format!("{}: {}", label, letters));
+            }
+        }
+        return lines.join("\n");
+    }
+
+    let mut lines: Vec<String> = Vec::new();
+    for (indent, row) in KEYBOARD_ROWS.iter().enumerate() {
+        let mut line = " ".repeat(indent * 2);
+        for letter in alphabet::letters(row) {
+            let key = format!(" {} ", letter.to_uppercase());
+            match states.iter().find(|(known, _)| *known == letter) {
+                Some((_, state)) => line.push_str(&paint(&key, *state)),
+                None => line.push_str(&key),
+            }
+        }
+        lines.push(line);
+    }
+    // letters from other alphabets like 'ñ' aren't on the keyboard, they get a row of their own once played
+    let extra: String = states.iter()
+        .filter(|(letter, _)| !KEYBOARD_ROWS.iter().any(|row| row.contains(letter)))
+        .map(|(letter, state)| paint(&format!(" {} ", letter.to_uppercase()), *state))
+        .collect();
+    if !extra.is_empty() {
+        lines.push(extra);
+    }
+    return lines.join("\n");
+}
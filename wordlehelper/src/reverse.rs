@@ -0,0 +1,99 @@
+/*
+* reverse solver. works out the answer from other people's share grids, no letters needed.
+* a row can only show up if some word in the list gives that pattern against the answer,
+* so the answers that can explain every row people posted rank highest.
+*/
+
+use serde_json::json;
+
+use crate::alphabet;
+use crate::game;
+use crate::game::PatternMatrix;
+use crate::output;
+use crate::share::Share;
+
+pub struct Ranked<'a> {
+    pub answer: &'a str,
+    // how many of the posted patterns some guess could have made
+    pub explained: usize,
+    // log of how many guesses make each explained pattern, added up. common patterns are more believable
+    pub likelihood: f64,
+}
+
+// the different patterns across all shares. solved rows fit every answer so they're left out.
+// a row that isn't word_len squares long has no pattern, so it's an error
+pub fn posted_patterns(shares: &[Share], word_len: usize) -> Result<Vec<u16>, String> {
+    let mut patterns: Vec<u16> = Vec::new();
+    for share in shares.iter() {
+        share.check_rows(word_len)?;
+        for row in share.rows.iter() {
+            let code = game::encode_pattern(row);
+            if !game::is_solved(row) && !patterns.contains(&code) {
+                patterns.push(code);
+            }
+        }
+    }
+    return Ok(patterns);
+}
+
+// rank every answer in the matrix, best first
+pub fn rank_answers<'a>(matrix: &PatternMatrix<'a>, patterns: &[u16]) -> Vec<Ranked<'a>> {
+    let word_len = matrix.answers.first().map(|answer| alphabet::letter_count(answer)).unwrap_or(0);
+    let mut ranked: Vec<Ranked> = Vec::new();
+    for (answer_index, answer) in matrix.answers.iter().enumerate() {
+        // how many guesses give each pattern against this answer
+        let mut ways = vec![0usize; game::pattern_count(word_len)];
+        for guess_index in 0..matrix.guesses.len() {
+            ways[matrix.get(guess_index, answer_index) as usize] += 1;
+        }
+
+        let mut explained = 0;
+        let mut likelihood = 0.0;
+        for code in patterns.iter() {
+            let count = ways[*code as usize];
+            if count > 0 {
+                explained += 1;
+                likelihood += (count as f64).ln();
+            }
+        }
+        ranked.push(Ranked { answer, explained, likelihood });
+    }
+
+    ranked.sort_by(|a, b| {
+        b.explained.cmp(&a.explained)
+            .then(b.likelihood.total_cmp(&a.likelihood))
+            .then(a.answer.cmp(b.answer))
+    });
+    return ranked;
+}
+
+pub fn print_ranking(ranked: &[Ranked], patterns: usize, top: usize) {
+    if !output::is_text() {
+        for (rank, entry) in ranked.iter().take(top).enumerate() {
+            output::record("rank", json!({ "rank": rank + 1, "answer": entry.answer, "explained": entry.explained, "patterns": patterns, "likelihood": entry.likelihood }));
+        }
+        return;
+    }
+    println!("rank  answer  explained  likelihood");
+    for (rank, entry) in ranked.iter().take(top).enumerate() {
+        println!("{:>4}  {:<6}  {:>5}/{:<3}  {:>10.1}", rank + 1, entry.answer, entry.explained, patterns, entry.likelihood);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::share;
+
+    #[test]
+    fn patterns_from_the_shares() {
+        let shares = share::parse_shares("Wordle 5 2/6\n⬛🟨⬛⬛⬛\n🟩🟩🟩🟩🟩\nWordle 5 3/6\n⬛🟨⬛⬛⬛\n🟩⬛⬛⬛🟩\n🟩🟩🟩🟩🟩\n").unwrap();
+        assert_eq!(posted_patterns(&shares, 5), Ok(vec![game::encode_pattern(&[0, 1, 0, 0, 0]), game::encode_pattern(&[2, 0, 0, 0, 2])]));
+    }
+
+    #[test]
+    fn rows_of_the_wrong_length_are_rejected() {
+        let shares = share::parse_shares("Wordle 5 2/6\n⬛🟨⬛⬛⬛\n🟩🟩🟩🟩🟩\n\nWordle 5 2/6\n⬛🟨⬛⬛⬛⬛\n🟩🟩🟩🟩🟩\n").unwrap();
+        assert_eq!(posted_patterns(&shares, 5), Err("Line 6: row 1 of puzzle 5 (⬛🟨⬛⬛⬛⬛) has 6 squares but the answer has 5 letters".to_string()));
+    }
+}
@@ -0,0 +1,37 @@
+/*
+* small seedable random number generator (splitmix64). good enough for picking practice answers,
+* and a seed makes a game repeatable.
+*/
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        return Rng { state: seed };
+    }
+
+    // pick a seed from the clock when the user didn't give one
+    pub fn random_seed() -> u64 {
+        return match SystemTime::now().duration_since(UNIX_EPOCH) {
+            Ok(time) => time.as_nanos() as u64,
+            Err(_) => 0,
+        };
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        return z ^ (z >> 31);
+    }
+
+    // a number in 0..n
+    pub fn below(&mut self, n: usize) -> usize {
+        return (self.next_u64() % n as u64) as usize;
+    }
+}
@@ -0,0 +1,329 @@
+/*
+* a local http api for the solver, so a web page can ask for suggestions. only built with the server feature.
+* the word lists and the pattern matrix are worked out once at startup and kept for every request.
+*   POST /suggest     {"history": [{"guess": "crane", "result": "00120"}], "strategy": "entropy"}
+*                     -> {"guess": "...", "remaining": 12}
+*   GET  /candidates  ?history=crane:00120,slate:00000&limit=20 -> {"count": 12, "candidates": [...]}
+*                     the query can be percent encoded, like history=crane%3A00120
+*   POST /score       {"answer": "tiger", "guess": "crane"} -> {"result": "00010", "solved": false}
+* it only listens on localhost.
+*/
+
+use std::collections::HashMap;
+
+use serde_json::{json, Value};
+use tiny_http::{Header, Method, Response, Server};
+
+use crate::alphabet;
+use crate::alphabet::Alphabet;
+use crate::game;
+use crate::game::{PatternMatrix, Pools};
+use crate::player;
+use crate::player::Strategy;
+
+pub struct Api<'a> {
+    pools: &'a Pools<'a>,
+    // every allowed guess scored against every possible answer
+    matrix: PatternMatrix<'a>,
+    guess_index: HashMap<&'a str, usize>,
+    // guesses have to be words the game could have, letters from the lists' alphabet and as long as the answers
+    alphabet: Alphabet,
+    word_len: usize,
+    // used when a request doesn't pick one
+    strategy: Strategy,
+}
+
+// a failed request, the status code and what went wrong
+type Failure = (u16, String);
+
+impl<'a> Api<'a> {
+    pub fn new(pools: &'a Pools<'a>, alphabet: &Alphabet, strategy: Strategy) -> Api<'a> {
+        let guesses: Vec<&str> = pools.allowed.iter().copied().collect();
+        let guess_index = guesses.iter().enumerate().map(|(i, guess_word)| (*guess_word, i)).collect();
+        let matrix = PatternMatrix::new(&guesses, &pools.possible);
+        return Api { pools, matrix, guess_index, alphabet: alphabet.clone(), word_len: pools.packed.word_len, strategy };
+    }
+
+    // answer one request. the url still has its query string on it
+    pub fn handle(&self, method: &str, url: &str, body: &str) -> (u16, Value) {
+        let (path, query) = url.split_once('?').unwrap_or((url, ""));
+        let response = match (method, path) {
+            ("POST", "/suggest") => parse_body(body).and_then(|request| self.suggest(&request)),
+            ("GET", "/candidates") => self.candidates(query),
+            ("POST", "/score") => parse_body(body).and_then(|request| self.score(&request)),
+            (_, "/suggest") | (_, "/candidates") | (_, "/score") => Err((405, format!("{} isn't allowed on {}", method, path))),
+            _ => Err((404, format!("There's nothing at {}", path))),
+        };
+        return match response {
+            Ok(value) => (200, value),
+            Err((status, error)) => (status, json!({ "error": error })),
+        };
+    }
+
+    fn suggest(&self, request: &Value) -> Result<Value, Failure> {
+        let history = match request.get("history") {
+            Some(Value::Array(entries)) => entries.iter().map(|entry| self.history_entry(entry)).collect::<Result<Vec<_>, _>>()?,
+            None => Vec::new(),
+            Some(_) => return Err((400, "history should be a list".to_string())),
+        };
+        let strategy = match request.get("strategy").and_then(|strategy| strategy.as_str()) {
+            Some(name) => Strategy::parse(name).map_err(|error| (400, error))?,
+            None => self.strategy,
+        };
+
+        let candidates = self.remaining(&history);
+        let board_state: HashMap<String,Vec<u8>> = history.iter().cloned().collect();
+        let guess_word = player::best_guess(strategy, self.pools, &candidates, &board_state, &[], history.len());
+        return Ok(json!({ "guess": guess_word, "remaining": candidates.len() }));
+    }
+
+    fn candidates(&self, query: &str) -> Result<Value, Failure> {
+        let mut history: Vec<(String,Vec<u8>)> = Vec::new();
+        let mut limit = usize::MAX;
+        for (key, value) in query.split('&').filter_map(|pair| pair.split_once('=')) {
+            // browsers encode the ':' and ',' in the history
+            let value = percent_decode(value)?;
+            let value = value.as_str();
+            match key {
+                // entries like crane:00120, split by commas
+                "history" => for entry in value.split(',').filter(|entry| !entry.is_empty()) {
+                    let (guess_word, result) = entry.split_once(':').ok_or((400, format!("Could not read '{}', use guess:result like crane:00120", entry)))?;
+                    history.push(self.parse_guess(guess_word, result)?);
+                },
+                "limit" => limit = value.parse().map_err(|_| (400, format!("Could not read the limit '{}'", value)))?,
+                _ => {}
+            }
+        }
+        let candidates = self.remaining(&history);
+        let shown: Vec<&str> = candidates.iter().take(limit).copied().collect();
+        return Ok(json!({ "count": candidates.len(), "candidates": shown }));
+    }
+
+    fn score(&self, request: &Value) -> Result<Value, Failure> {
+        let field = |name: &str| request.get(name).and_then(|value| value.as_str()).map(alphabet::normalize)
+            .ok_or((400, format!("'{}' is missing", name)));
+        let answer = field("answer")?;
+        let guess_word = field("guess")?;
+        if alphabet::letter_count(&answer) != alphabet::letter_count(&guess_word) {
+            return Err((400, format!("'{}' and '{}' are different lengths", answer, guess_word)));
+        }
+        let state_vec = game::determine_board_results(&answer, &guess_word);
+        let result: String = state_vec.iter().map(|state| state.to_string()).collect();
+        return Ok(json!({ "result": result, "solved": game::is_solved(&state_vec) }));
+    }
+
+    // the possible answers that fit every guess so far. guesses from the list use the pattern matrix
+    fn remaining(&self, history: &[(String,Vec<u8>)]) -> Vec<&'a str> {
+        let mut answer_indexes: Vec<usize> = (0..self.matrix.answers.len()).collect();
+        for (guess_word, state_vec) in history.iter() {
+            let code = game::encode_pattern(state_vec);
+            answer_indexes = match self.guess_index.get(guess_word.as_str()) {
+                Some(guess_index) => answer_indexes.into_iter().filter(|answer_index| self.matrix.get(*guess_index, *answer_index) == code).collect(),
+                None => answer_indexes.into_iter().filter(|answer_index| game::pattern_code(self.matrix.answers[*answer_index], guess_word) == code).collect(),
+            };
+        }
+        return answer_indexes.iter().map(|answer_index| self.matrix.answers[*answer_index]).collect();
+    }
+
+    // {"guess": "crane", "result": "00120"}
+    fn history_entry(&self, entry: &Value) -> Result<(String,Vec<u8>), Failure> {
+        return match (entry.get("guess").and_then(|guess| guess.as_str()), entry.get("result").and_then(|result| result.as_str())) {
+            (Some(guess_word), Some(result)) => self.parse_guess(guess_word, result),
+            _ => Err((400, format!("Could not read the history entry {}, it needs a guess and a result", entry))),
+        };
+    }
+
+    // a guess has to be a word the answers could be scored against, and its result as long as it is
+    fn parse_guess(&self, guess_word: &str, result: &str) -> Result<(String,Vec<u8>), Failure> {
+        let guess_word = alphabet::normalize(guess_word);
+        let unknown = self.alphabet.unknown_letters(&guess_word);
+        if !unknown.is_empty() {
+            return Err((400, format!("'{}' has letters that aren't in the alphabet: {}", guess_word, unknown.join(" "))));
+        }
+        if alphabet::letter_count(&guess_word) != self.word_len {
+            return Err((400, format!("'{}' isn't {} letters long", guess_word, self.word_len)));
+        }
+        let state_vec = game::parse_colors(result).map_err(|error| (400, error))?;
+        if state_vec.len() != self.word_len {
+            return Err((400, format!("'{}' and '{}' are different lengths", guess_word, result)));
+        }
+        return Ok((guess_word, state_vec));
+    }
+}
+
+// run until the process is stopped
+pub fn serve(pools: &Pools, alphabet: &Alphabet, strategy: Strategy, port: u16) -> Result<(), String> {
+    let server = Server::http(("127.0.0.1", port)).map_err(|error| format!("Could not listen on port {}: {}", port, error))?;
+    println!("working out the pattern matrix...");
+    let api = Api::new(pools, alphabet, strategy);
+    println!("listening on http://127.0.0.1:{}", port);
+    for request in server.incoming_requests() {
+        respond(&api, request);
+    }
+    return Ok(());
+}
+
+fn respond(api: &Api, mut request: tiny_http::Request) {
+    let mut body = String::new();
+    let (status, value) = match request.as_reader().read_to_string(&mut body) {
+        Ok(_) => api.handle(method_name(request.method()), request.url(), &body),
+        Err(error) => (400, json!({ "error": format!("Could not read the request: {}", error) })),
+    };
+    let header = Header::from_bytes("Content-Type", "application/json").expect("the header is valid");
+    let response = Response::from_string(value.to_string()).with_status_code(status).with_header(header);
+    if let Err(error) = request.respond(response) {
+        println!("warning: could not send a response: {}", error);
+    }
+}
+
+fn method_name(method: &Method) -> &str {
+    return match method {
+        Method::Get => "GET",
+        Method::Post => "POST",
+        _ => "OTHER",
+    };
+}
+
+fn parse_body(body: &str) -> Result<Value, Failure> {
+    return serde_json::from_str(body).map_err(|error| (400, format!("The body isn't json: {}", error)));
+}
+
+// undo the %3A style escapes in a query value. a '+' stays a '+', nerdle guesses have them
+fn percent_decode(text: &str) -> Result<String, Failure> {
+    let bytes = text.as_bytes();
+    let mut decoded: Vec<u8> = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' => {
+                let byte = text.get(i + 1..i + 3).filter(|hex| hex.bytes().all(|digit| digit.is_ascii_hexdigit()))
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                    .ok_or((400, format!("Could not read the escape in '{}'", text)))?;
+                decoded.push(byte);
+                i += 3;
+            }
+            byte => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    return String::from_utf8(decoded).map_err(|_| (400, format!("'{}' isn't utf-8 once it's decoded", text)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::Feedback;
+    use crate::prior::Prior;
+    use std::io::{Read, Write};
+    use std::net::TcpStream;
+
+    const WORDS: [&str; 6] = ["crane", "tiger", "slate", "timer", "tiler", "ozone"];
+    const ANSWERS: [&str; 4] = ["tiger", "timer", "tiler", "ozone"];
+
+    fn pools() -> Pools<'static> {
+        return Pools::new(&WORDS, &ANSWERS, Prior::uniform(), Feedback::Colors);
+    }
+
+    #[test]
+    fn score_matches_the_game() {
+        let pools = pools();
+        let api = Api::new(&pools, &Alphabet::english(), Strategy::Entropy);
+        let (status, value) = api.handle("POST", "/score", r#"{"answer": "tiger", "guess": "crane"}"#);
+        assert_eq!(status, 200);
+        assert_eq!(value, json!({ "result": "01001", "solved": false }));
+    }
+
+    #[test]
+    fn candidates_follow_the_history() {
+        let pools = pools();
+        let api = Api::new(&pools, &Alphabet::english(), Strategy::Entropy);
+        let (status, value) = api.handle("GET", "/candidates?history=crane:01001", "");
+        assert_eq!(status, 200);
+        assert_eq!(value, json!({ "count": 3, "candidates": ["tiger", "timer", "tiler"] }));
+        let (_, value) = api.handle("GET", "/candidates?history=crane:01001&limit=1", "");
+        assert_eq!(value["candidates"], json!(["tiger"]));
+    }
+
+    #[test]
+    fn suggest_picks_a_word() {
+        let pools = pools();
+        let api = Api::new(&pools, &Alphabet::english(), Strategy::Entropy);
+        let (status, value) = api.handle("POST", "/suggest", r#"{"history": [{"guess": "crane", "result": "01001"}]}"#);
+        assert_eq!(status, 200);
+        assert_eq!(value["remaining"], json!(3));
+        assert!(WORDS.contains(&value["guess"].as_str().unwrap()));
+    }
+
+    #[test]
+    fn bad_requests_say_why() {
+        let pools = pools();
+        let api = Api::new(&pools, &Alphabet::english(), Strategy::Entropy);
+        assert_eq!(api.handle("POST", "/suggest", "not json").0, 400);
+        assert_eq!(api.handle("POST", "/score", r#"{"answer": "tiger"}"#).0, 400);
+        assert_eq!(api.handle("GET", "/candidates?history=crane:0120", "").0, 400);
+        assert_eq!(api.handle("GET", "/candidates?history=crane%3", "").0, 400);
+        assert_eq!(api.handle("POST", "/suggest", r#"{"history": [{"guess": "cr4ne", "result": "00000"}]}"#).0, 400);
+        assert_eq!(api.handle("GET", "/score", "").0, 405);
+        assert_eq!(api.handle("GET", "/nowhere", "").0, 404);
+    }
+
+    // send each request to a server on localhost and collect the responses
+    fn over_localhost(requests: &[String]) -> Vec<String> {
+        let pools = pools();
+        let api = Api::new(&pools, &Alphabet::english(), Strategy::Entropy);
+        let server = Server::http("127.0.0.1:0").unwrap();
+        let port = server.server_addr().to_ip().unwrap().port();
+        return std::thread::scope(|scope| {
+            scope.spawn(|| {
+                for _ in requests.iter() {
+                    respond(&api, server.recv().unwrap());
+                }
+            });
+            return requests.iter().map(|request| {
+                let mut stream = TcpStream::connect(("127.0.0.1", port)).unwrap();
+                stream.write_all(request.as_bytes()).unwrap();
+                let mut response = String::new();
+                stream.read_to_string(&mut response).unwrap();
+                response
+            }).collect();
+        });
+    }
+
+    fn post(path: &str, body: &str) -> String {
+        return format!("POST {} HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}", path, body.len(), body);
+    }
+
+    fn get(url: &str) -> String {
+        return format!("GET {} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n", url);
+    }
+
+    #[test]
+    fn serves_over_localhost() {
+        let responses = over_localhost(&[post("/score", r#"{"answer": "tiger", "guess": "tiger"}"#)]);
+        assert!(responses[0].starts_with("HTTP/1.1 200"));
+        assert!(responses[0].ends_with(r#"{"result":"22222","solved":true}"#));
+    }
+
+    #[test]
+    fn guesses_that_dont_fit_the_game_are_bad_requests() {
+        // the server has to keep going after them
+        let responses = over_localhost(&[
+            post("/suggest", r#"{"history": [{"guess": "cranes", "result": "000000"}]}"#),
+            get("/candidates?history=cranes:000000"),
+            post("/suggest", r#"{"history": [{"guess": "crane", "result": "01001"}]}"#),
+        ]);
+        assert!(responses[0].starts_with("HTTP/1.1 400"));
+        assert!(responses[0].ends_with(r#"{"error":"'cranes' isn't 5 letters long"}"#));
+        assert!(responses[1].starts_with("HTTP/1.1 400"));
+        assert!(responses[2].starts_with("HTTP/1.1 200"));
+    }
+
+    #[test]
+    fn candidates_query_can_be_percent_encoded() {
+        let responses = over_localhost(&[get("/candidates?history=crane%3A01001%2Cslate%3a00011&limit=5")]);
+        assert!(responses[0].starts_with("HTTP/1.1 200"));
+        assert!(responses[0].ends_with(r#"{"count":2,"candidates":["tiger","timer"]}"#));
+    }
+}
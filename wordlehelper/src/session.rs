@@ -0,0 +1,187 @@
+/*
+* saved games. an interactive game is written to session.json after every guess, so closing the terminal
+* doesn't lose it and 'i --resume' picks it up again with the same puzzle and settings.
+* finished games are added to games.jsonl, a line each, for keeping stats.
+* both files live in --state-dir, which defaults to $XDG_STATE_HOME/wordlehelper or ~/.local/state/wordlehelper.
+*/
+
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use serde_json::{json, Value};
+
+use crate::calendar;
+use crate::cli::Game;
+use crate::game::Feedback;
+use crate::player::Strategy;
+
+const SESSION_FILE: &str = "session.json";
+pub const GAMES_FILE: &str = "games.jsonl";
+
+// a game in progress and everything needed to carry on with it
+pub struct Session {
+    pub game: Game,
+    pub puzzle: usize,
+    pub strategy: Strategy,
+    pub feedback: Feedback,
+    pub history: Vec<(String,Vec<u8>)>,
+}
+
+// where the files go. the directory is made when something is first saved
+pub fn state_dir(given: Option<&str>) -> Result<PathBuf, String> {
+    if let Some(dir) = given {
+        return Ok(PathBuf::from(dir));
+    }
+    if let Some(dir) = std::env::var_os("XDG_STATE_HOME").filter(|dir| !dir.is_empty()) {
+        return Ok(PathBuf::from(dir).join("wordlehelper"));
+    }
+    return match std::env::var_os("HOME") {
+        Some(home) => Ok(PathBuf::from(home).join(".local").join("state").join("wordlehelper")),
+        None => Err("Could not find a home directory to save games in, use --state-dir".to_string()),
+    };
+}
+
+impl Session {
+    pub fn load(dir: &Path) -> Result<Session, String> {
+        let path = dir.join(SESSION_FILE);
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Err(format!("There's no saved game to resume in {}", dir.display())),
+            Err(error) => return Err(format!("Could not read {}: {}", path.display(), error)),
+        };
+        let value: Value = serde_json::from_str(&text).map_err(|error| format!("{} is broken: {}", path.display(), error))?;
+        let field = |name: &str| value.get(name).and_then(|field| field.as_str()).ok_or(format!("{} has no {}", path.display(), name));
+        let puzzle = value.get("puzzle").and_then(|puzzle| puzzle.as_u64()).ok_or(format!("{} has no puzzle", path.display()))?;
+        return Ok(Session {
+            game: Game::parse(field("game")?)?,
+            puzzle: puzzle as usize,
+            strategy: Strategy::parse(field("strategy")?)?,
+            feedback: Feedback::parse(field("feedback")?)?,
+            history: parse_history(value.get("history")).ok_or(format!("{} has a broken history", path.display()))?,
+        });
+    }
+
+    // write the whole session again. it goes to a temporary file first so a crash can't leave half a file
+    pub fn save(&self, dir: &Path) -> Result<(), String> {
+        let value = json!({
+            "game": self.game.name(),
+            "puzzle": self.puzzle,
+            "strategy": self.strategy.name(),
+            "feedback": self.feedback.name(),
+            "history": history_value(&self.history),
+        });
+        fs::create_dir_all(dir).map_err(|error| format!("Could not make {}: {}", dir.display(), error))?;
+        let path = dir.join(SESSION_FILE);
+        let temporary = dir.join(format!("{}.tmp", SESSION_FILE));
+        fs::write(&temporary, value.to_string())
+            .and_then(|_| fs::rename(&temporary, &path))
+            .map_err(|error| format!("Could not save the game to {}: {}", path.display(), error))?;
+        return Ok(());
+    }
+
+    // the game is over, add it to the history of games and forget the session
+    pub fn finish(&self, dir: &Path, solved: bool) -> Result<(), String> {
+        let record = json!({
+            "date": calendar::today().to_string(),
+            "game": self.game.name(),
+            "puzzle": self.puzzle,
+            "strategy": self.strategy.name(),
+            "feedback": self.feedback.name(),
+            "solved": solved,
+            "guesses": self.history.len(),
+            "history": history_value(&self.history),
+        });
+        fs::create_dir_all(dir).map_err(|error| format!("Could not make {}: {}", dir.display(), error))?;
+        let path = dir.join(GAMES_FILE);
+        fs::OpenOptions::new().create(true).append(true).open(&path)
+            .and_then(|mut file| writeln!(file, "{}", record))
+            .map_err(|error| format!("Could not add the game to {}: {}", path.display(), error))?;
+        return match fs::remove_file(dir.join(SESSION_FILE)) {
+            Err(error) if error.kind() != std::io::ErrorKind::NotFound => Err(format!("Could not remove the finished session: {}", error)),
+            _ => Ok(()),
+        };
+    }
+}
+
+// a line from the history of games
+pub struct FinishedGame {
+    pub game: String,
+    pub feedback: String,
+    pub puzzle: usize,
+    pub solved: bool,
+    pub guesses: usize,
+}
+
+// every finished game in the order they were played, and how many lines couldn't be read
+pub fn load_finished(dir: &Path) -> Result<(Vec<FinishedGame>, usize), String> {
+    let path = dir.join(GAMES_FILE);
+    let text = match fs::read_to_string(&path) {
+        Ok(text) => text,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok((Vec::new(), 0)),
+        Err(error) => return Err(format!("Could not read {}: {}", path.display(), error)),
+    };
+    let mut games: Vec<FinishedGame> = Vec::new();
+    let mut broken = 0;
+    for line in text.lines().filter(|line| !line.trim().is_empty()) {
+        match serde_json::from_str::<Value>(line).ok().and_then(|value| finished_game(&value)) {
+            Some(game) => games.push(game),
+            None => broken += 1,
+        }
+    }
+    return Ok((games, broken));
+}
+
+fn finished_game(value: &Value) -> Option<FinishedGame> {
+    return Some(FinishedGame {
+        game: value.get("game")?.as_str()?.to_string(),
+        feedback: value.get("feedback")?.as_str()?.to_string(),
+        puzzle: value.get("puzzle")?.as_u64()? as usize,
+        solved: value.get("solved")?.as_bool()?,
+        guesses: value.get("guesses")?.as_u64()? as usize,
+    });
+}
+
+// [{"guess": "crane", "result": [0, 0, 1, 2, 0]}], the results are numbers so every kind of feedback fits
+pub fn history_value(history: &[(String,Vec<u8>)]) -> Value {
+    return history.iter().map(|(guess_word, results)| json!({ "guess": guess_word, "result": results })).collect();
+}
+
+pub fn parse_history(value: Option<&Value>) -> Option<Vec<(String,Vec<u8>)>> {
+    let mut history: Vec<(String,Vec<u8>)> = Vec::new();
+    for entry in value?.as_array()?.iter() {
+        let guess_word = entry.get("guess")?.as_str()?.to_string();
+        let results: Option<Vec<u8>> = entry.get("result")?.as_array()?.iter().map(|result| result.as_u64().map(|result| result as u8)).collect();
+        history.push((guess_word, results?));
+    }
+    return Some(history);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sessions_survive_a_round_trip() {
+        let dir = std::env::temp_dir().join(format!("wordlehelper-session-{}", std::process::id()));
+        let session = Session {
+            game: Game::Wordle,
+            puzzle: 200,
+            strategy: Strategy::Entropy,
+            feedback: Feedback::Colors,
+            history: vec![("crane".to_string(), vec![0, 1, 0, 0, 1])],
+        };
+        session.save(&dir).unwrap();
+        let loaded = Session::load(&dir).unwrap();
+        assert_eq!(loaded.puzzle, 200);
+        assert_eq!(loaded.strategy.name(), "entropy");
+        assert_eq!(loaded.history, session.history);
+
+        session.finish(&dir, false).unwrap();
+        assert!(Session::load(&dir).is_err());
+        let (games, broken) = load_finished(&dir).unwrap();
+        assert_eq!((games.len(), broken), (1, 0));
+        assert_eq!((games[0].puzzle, games[0].solved, games[0].guesses), (200, false, 1));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
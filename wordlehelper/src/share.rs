@@ -0,0 +1,199 @@
+/*
+* the emoji grids wordle gives you to share a game, like
+*   Wordle 257 4/6
+*   ⬛🟨⬛⬛⬛
+*   ...
+* builds them from a game and reads pasted ones back into results.
+*/
+
+use std::collections::BTreeSet;
+
+use serde_json::json;
+
+use crate::alphabet;
+use crate::cli::Game;
+use crate::game;
+use crate::game::MAX_GUESSES;
+use crate::output;
+
+pub struct Share {
+    pub puzzle: usize,
+    // one hot encoded result per row, same as the board state
+    pub rows: Vec<Vec<u8>>,
+    // the line of the pasted text each row was on, counting from 1
+    pub lines: Vec<usize>,
+}
+
+impl Share {
+    // no guess can make a row that isn't as long as the answer, so it's a bad paste
+    pub fn check_rows(&self, word_len: usize) -> Result<(), String> {
+        for (turn, row) in self.rows.iter().enumerate() {
+            if row.len() != word_len {
+                let row_emoji: String = row.iter().map(|state| emoji(*state)).collect();
+                return Err(format!("Line {}: row {} of puzzle {} ({}) has {} squares but the answer has {} letters", self.lines[turn], turn + 1, self.puzzle, row_emoji, row.len(), word_len));
+            }
+        }
+        return Ok(());
+    }
+}
+
+fn emoji(state: u8) -> char {
+    return match state {
+        2 => '🟩',
+        1 => '🟨',
+        _ => '⬛',
+    };
+}
+
+// high contrast mode shares orange and blue instead of green and yellow, light mode uses white squares
+fn state_from_emoji(square: char) -> Option<u8> {
+    return match square {
+        '🟩' | '🟧' => Some(2),
+        '🟨' | '🟦' => Some(1),
+        '⬛' | '⬜' => Some(0),
+        _ => None,
+    };
+}
+
+// the shareable grid for a game. unsolved games score X like the real thing
+pub fn emoji_grid(game: Game, puzzle: usize, history: &[(String,Vec<u8>)]) -> String {
+    let solved = history.last().is_some_and(|(_, state_vec)| game::is_solved(state_vec));
+    let score = if solved { history.len().to_string() } else { "X".to_string() };
+    let mut grid = format!("{} {} {}/{}", game.share_title(), puzzle, score, MAX_GUESSES);
+    for (_, state_vec) in history.iter() {
+        grid.push('\n');
+        grid.extend(state_vec.iter().map(|state| emoji(*state)));
+    }
+    return grid;
+}
+
+// read a pasted grid. blank lines and anything after the grid (links, hashtags) are skipped
+pub fn parse_share(text: &str) -> Result<Share, String> {
+    return parse_block(text, 1);
+}
+
+// a grid that starts on first_line of what was pasted
+fn parse_block(text: &str, first_line: usize) -> Result<Share, String> {
+    let mut lines = text.lines().enumerate().map(|(i, line)| (first_line + i, line.trim())).filter(|(_, line)| !line.is_empty());
+    let header = match lines.next() {
+        Some((_, header)) => header,
+        None => return Err("The share is empty".to_string()),
+    };
+    let puzzle = parse_header(header)?;
+
+    let mut rows: Vec<Vec<u8>> = Vec::new();
+    let mut row_lines: Vec<usize> = Vec::new();
+    for (line_number, line) in lines {
+        // emoji can carry variation selectors, skip those
+        let squares: Vec<char> = line.chars().filter(|square| *square != '\u{fe0f}').collect();
+        let row: Option<Vec<u8>> = squares.iter().map(|square| state_from_emoji(*square)).collect();
+        match row {
+            Some(row) if !row.is_empty() => {
+                rows.push(row);
+                row_lines.push(line_number);
+            }
+            // the grid is over once we hit a line that isn't squares
+            _ if !rows.is_empty() => break,
+            _ => return Err(format!("Line {}: could not read the row '{}'", line_number, line)),
+        }
+    }
+    if rows.is_empty() {
+        return Err("The share has no rows".to_string());
+    }
+    return Ok(Share { puzzle, rows, lines: row_lines });
+}
+
+// read several pasted grids one after the other. each one starts at its 'Wordle' header
+pub fn parse_shares(text: &str) -> Result<Vec<Share>, String> {
+    // each block is its text and the line it starts on
+    let mut blocks: Vec<(String, usize)> = Vec::new();
+    for (i, line) in text.lines().enumerate() {
+        if line.trim_start().to_lowercase().starts_with("wordle") || blocks.is_empty() {
+            blocks.push((String::new(), i + 1));
+        }
+        let (block, _) = blocks.last_mut().expect("a block was just pushed");
+        block.push_str(line);
+        block.push('\n');
+    }
+    let blocks: Vec<&(String, usize)> = blocks.iter().filter(|(block, _)| !block.trim().is_empty()).collect();
+    if blocks.is_empty() {
+        return Err("No shares were given".to_string());
+    }
+    return blocks.iter().map(|(block, first_line)| parse_block(block, *first_line)).collect();
+}
+
+// "Wordle 1,234 4/6*" -> 1234
+fn parse_header(header: &str) -> Result<usize, String> {
+    let parts: Vec<&str> = header.split_whitespace().collect();
+    if parts.len() < 2 || !parts[0].eq_ignore_ascii_case("wordle") {
+        return Err(format!("Could not read the header '{}'. It should look like 'Wordle 257 4/6'", header));
+    }
+    return parts[1].replace([',', '.'], "").parse::<usize>()
+        .map_err(|_| format!("Could not read the puzzle number in '{}'", header));
+}
+
+// every word that gives each row's result against the answer, so you can see which guesses made the grid
+pub fn import(words: &BTreeSet<&str>, answers: &[&str], share: &Share) -> Result<(), String> {
+    let answer = match answers.get(share.puzzle) {
+        Some(answer) => *answer,
+        None => return Err(format!("There is no answer for puzzle {} in the answer list", share.puzzle)),
+    };
+    share.check_rows(alphabet::letter_count(answer))?;
+    // the set is already in order, so the words come out sorted
+    let words: Vec<&str> = words.iter().copied().filter(|word| alphabet::letter_count(word) == alphabet::letter_count(answer)).collect();
+
+    if output::is_text() {
+        println!("puzzle {}, answer '{}'", share.puzzle, answer);
+    }
+    for (turn, row) in share.rows.iter().enumerate() {
+        let code = game::encode_pattern(row);
+        let fits: Vec<&str> = words.iter().copied().filter(|word| game::pattern_code(answer, word) == code).collect();
+        if !output::is_text() {
+            let pattern: String = row.iter().map(|state| state.to_string()).collect();
+            output::record("row", json!({ "puzzle": share.puzzle, "answer": answer, "turn": turn + 1, "pattern": pattern, "count": fits.len(), "words": fits }));
+            continue
+        }
+        let row_emoji: String = row.iter().map(|state| emoji(*state)).collect();
+        println!("{} {} : {} words", turn + 1, row_emoji, fits.len());
+        println!("  {}", fits.join(" "));
+    }
+    return Ok(());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_a_pasted_grid() {
+        let share = parse_share("Wordle 1,234 3/6*\n\n⬛🟨⬛⬛⬛\n🟩⬜🟩🟦⬛\n🟧🟧🟧🟧🟧\n\n#wordle").unwrap();
+        assert_eq!(share.puzzle, 1234);
+        assert_eq!(share.rows, vec![vec![0, 1, 0, 0, 0], vec![2, 0, 2, 1, 0], vec![2, 2, 2, 2, 2]]);
+        assert_eq!(share.lines, vec![3, 4, 5]);
+        assert!(parse_share("Wordle 257 1/6\n⬛🟨x⬛⬛").is_err());
+        assert!(parse_share("257 1/6\n🟩🟩🟩🟩🟩").is_err());
+    }
+
+    #[test]
+    fn grid_round_trips() {
+        let history = vec![("roate".to_string(), vec![0, 0, 1, 2, 0]), ("basic".to_string(), vec![2, 2, 2, 2, 2])];
+        let share = parse_share(&emoji_grid(Game::Wordle, 100, &history)).unwrap();
+        assert_eq!(share.puzzle, 100);
+        assert_eq!(share.rows, vec![vec![0, 0, 1, 2, 0], vec![2, 2, 2, 2, 2]]);
+    }
+
+    #[test]
+    fn nerdle_grids_say_nerdle() {
+        let history = vec![("12+35=47".to_string(), vec![2, 0, 0, 1, 0, 2, 0, 0]), ("12+2-6=8".to_string(), vec![2; 8])];
+        assert_eq!(emoji_grid(Game::Nerdle, 5, &history), "nerdlegame 5 2/6\n🟩⬛⬛🟨⬛🟩⬛⬛\n🟩🟩🟩🟩🟩🟩🟩🟩");
+    }
+
+    #[test]
+    fn rows_have_to_fit_the_answer() {
+        let share = parse_share("Wordle 0 X/6\n⬛🟨⬛⬛⬛\n⬛🟨⬛⬛\n").unwrap();
+        assert_eq!(share.check_rows(5), Err("Line 3: row 2 of puzzle 0 (⬛🟨⬛⬛) has 4 squares but the answer has 5 letters".to_string()));
+        let words: BTreeSet<&str> = ["crane", "crate", "trace"].into_iter().collect();
+        assert!(import(&words, &["trace"], &share).is_err());
+        assert!(share.check_rows(4).is_err());
+    }
+}
@@ -0,0 +1,157 @@
+/*
+* personal stats from the history of finished games, like the stats screen in the real game.
+* the solver plays every puzzle in the history too, so your guesses can be put next to its guesses.
+*/
+
+use serde_json::json;
+
+use crate::game::MAX_GUESSES;
+use crate::output;
+use crate::session::FinishedGame;
+
+pub struct Stats {
+    pub played: usize,
+    pub won: usize,
+    // puzzles solved in a row up to the latest one, and the longest run. skipping a puzzle ends a run
+    pub current_streak: usize,
+    pub best_streak: usize,
+    // how many games were solved in each number of guesses, the first entry is one guess
+    pub distribution: Vec<usize>,
+    pub solver_distribution: Vec<usize>,
+    // guesses on the games both you and the solver solved
+    pub compared: usize,
+    pub average: f64,
+    pub solver_average: f64,
+    // games where you used fewer, the same and more guesses than the solver
+    pub better: usize,
+    pub tied: usize,
+    pub worse: usize,
+}
+
+// solver has the solver's guesses for each game, None when it didn't solve it
+pub fn summarize(games: &[&FinishedGame], solver: &[Option<usize>]) -> Stats {
+    let longest = games.iter().map(|game| game.guesses).chain(solver.iter().flatten().copied()).max().unwrap_or(0).max(MAX_GUESSES);
+    let mut distribution = vec![0; longest];
+    let mut solver_distribution = vec![0; longest];
+    let (mut compared, mut total, mut solver_total) = (0, 0, 0);
+    let (mut better, mut tied, mut worse) = (0, 0, 0);
+    for (game, solver_guesses) in games.iter().zip(solver.iter()) {
+        if game.solved && game.guesses > 0 {
+            distribution[game.guesses - 1] += 1;
+        }
+        if let Some(solver_guesses) = solver_guesses.filter(|guesses| *guesses > 0) {
+            solver_distribution[solver_guesses - 1] += 1;
+            if game.solved {
+                compared += 1;
+                total += game.guesses;
+                solver_total += solver_guesses;
+                match game.guesses.cmp(&solver_guesses) {
+                    std::cmp::Ordering::Less => better += 1,
+                    std::cmp::Ordering::Equal => tied += 1,
+                    std::cmp::Ordering::Greater => worse += 1,
+                }
+            }
+        }
+    }
+
+    // streaks go by puzzle number, only the first try at a puzzle counts
+    let mut firsts: Vec<&FinishedGame> = Vec::new();
+    for game in games.iter() {
+        if !firsts.iter().any(|first| first.puzzle == game.puzzle) {
+            firsts.push(game);
+        }
+    }
+    firsts.sort_by_key(|game| game.puzzle);
+    let mut streak = 0;
+    let mut best_streak = 0;
+    let mut last_puzzle: Option<usize> = None;
+    for game in firsts.iter() {
+        let follows = last_puzzle.is_some_and(|last| last + 1 == game.puzzle);
+        streak = match (game.solved, follows) {
+            (false, _) => 0,
+            (true, true) => streak + 1,
+            (true, false) => 1,
+        };
+        best_streak = best_streak.max(streak);
+        last_puzzle = Some(game.puzzle);
+    }
+
+    let average = |sum: usize| if compared > 0 { sum as f64 / compared as f64 } else { 0.0 };
+    return Stats {
+        played: games.len(),
+        won: games.iter().filter(|game| game.solved).count(),
+        current_streak: streak,
+        best_streak,
+        distribution,
+        solver_distribution,
+        compared,
+        average: average(total),
+        solver_average: average(solver_total),
+        better,
+        tied,
+        worse,
+    };
+}
+
+pub fn print_stats(stats: &Stats) {
+    let win_rate = if stats.played > 0 { 100.0 * stats.won as f64 / stats.played as f64 } else { 0.0 };
+    if !output::is_text() {
+        output::record("stats", json!({
+            "played": stats.played,
+            "won": stats.won,
+            "win_rate": (win_rate * 10.0).round() / 10.0,
+            "current_streak": stats.current_streak,
+            "best_streak": stats.best_streak,
+            "compared": stats.compared,
+            "average": stats.average,
+            "solver_average": stats.solver_average,
+            "better": stats.better,
+            "tied": stats.tied,
+            "worse": stats.worse,
+        }));
+        for (i, (yours, solvers)) in stats.distribution.iter().zip(stats.solver_distribution.iter()).enumerate() {
+            output::record("distribution", json!({ "guesses": i + 1, "you": yours, "solver": solvers }));
+        }
+        return;
+    }
+
+    println!("played {}, won {} ({:.0}%), current streak {}, best streak {}", stats.played, stats.won, win_rate, stats.current_streak, stats.best_streak);
+    println!("guesses  you / solver");
+    let most = stats.distribution.iter().max().copied().unwrap_or(0).max(1);
+    // long games only get a row if something took that long
+    let rows = stats.distribution.iter().zip(stats.solver_distribution.iter()).enumerate()
+        .filter(|(i, (yours, solvers))| *i < MAX_GUESSES || **yours > 0 || **solvers > 0);
+    for (i, (yours, solvers)) in rows {
+        let bar = "#".repeat((yours * 30).div_ceil(most));
+        println!("{:>7}  {} {} / {}", i + 1, bar, yours, solvers);
+    }
+    println!("not solved: {}", stats.played - stats.won);
+    if stats.compared > 0 {
+        println!("on the {} puzzles you both solved you averaged {:.2} guesses and the solver averaged {:.2}",
+            stats.compared, stats.average, stats.solver_average);
+        println!("you beat the solver {} times, tied {} and lost {}", stats.better, stats.tied, stats.worse);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn game(puzzle: usize, solved: bool, guesses: usize) -> FinishedGame {
+        return FinishedGame { game: "wordle".to_string(), feedback: "colors".to_string(), puzzle, solved, guesses };
+    }
+
+    #[test]
+    fn streaks_and_distribution() {
+        let games = [game(10, true, 4), game(11, true, 3), game(12, false, 6), game(13, true, 3), game(14, true, 5), game(14, false, 6), game(16, true, 2)];
+        let games: Vec<&FinishedGame> = games.iter().collect();
+        let solver = [Some(4), Some(4), Some(5), Some(3), Some(4), Some(4), Some(3)];
+        let stats = summarize(&games, &solver);
+        assert_eq!((stats.played, stats.won), (7, 5));
+        // 15 was skipped, so 16 starts a new run
+        assert_eq!((stats.current_streak, stats.best_streak), (1, 2));
+        assert_eq!(stats.distribution, vec![0, 1, 2, 1, 1, 0]);
+        assert_eq!(stats.solver_distribution, vec![0, 0, 2, 4, 1, 0]);
+        assert_eq!((stats.compared, stats.better, stats.tied, stats.worse), (5, 2, 2, 1));
+    }
+}
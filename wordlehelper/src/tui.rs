@@ -0,0 +1,310 @@
+/*
+* a full screen version of interactive mode, 'i --tui'. only built with the tui feature.
+* the board fills in as you play, with the keyboard colored by what's known about each letter,
+* the solver's top guesses and their scores, and the candidates that are left.
+* the suggestion is typed in for you. pick a tile with the arrows and change its color with space
+* instead of typing '00120', then enter plays the row. esc leaves a game part way, it's saved to resume.
+*/
+
+use std::collections::{HashMap, HashSet};
+
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
+use ratatui::{DefaultTerminal, Frame};
+
+use crate::alphabet;
+use crate::game;
+use crate::game::{History, Pools, MAX_GUESSES};
+use crate::player;
+use crate::player::Strategy;
+use crate::render;
+use crate::wordlist;
+
+// how many guesses the suggestion list shows
+const TOP: usize = 10;
+// nobody reads more candidates than this, and wrapping thousands of them is slow
+const CANDIDATES_SHOWN: usize = 400;
+
+const HELP: &str = "type a guess  tab: next suggestion  left/right: pick a tile  space/up/down: change its color  enter: play  esc: quit";
+
+struct Tui<'a> {
+    pools: &'a Pools<'a>,
+    past_answers: &'a [&'a str],
+    strategy: Strategy,
+    history: Vec<(String,Vec<u8>)>,
+    board_state: HashMap<String,Vec<u8>>,
+    candidates: Vec<&'a str>,
+    // the solver's best guesses and their scores, its own pick first
+    suggestions: Vec<(String, f64)>,
+    // which suggestion tab is on
+    choice: usize,
+    // the row being entered, its letters and the colors picked for them
+    letters: Vec<String>,
+    colors: Vec<u8>,
+    selected: usize,
+    word_len: usize,
+    // the keys on screen, only the ones the word list uses
+    keys: Vec<Vec<String>>,
+    message: String,
+    over: bool,
+}
+
+impl<'a> Tui<'a> {
+    fn new(pools: &'a Pools<'a>, past_answers: &'a [&'a str], strategy: Strategy, resumed: Vec<(String,Vec<u8>)>) -> Tui<'a> {
+        let word_len = pools.possible.first().map(|word| alphabet::letter_count(word)).unwrap_or(wordlist::WORD_LEN);
+        let mut tui = Tui {
+            pools,
+            past_answers,
+            strategy,
+            history: Vec::new(),
+            board_state: HashMap::new(),
+            candidates: pools.possible.clone(),
+            suggestions: Vec::new(),
+            choice: 0,
+            letters: Vec::new(),
+            colors: Vec::new(),
+            selected: 0,
+            word_len,
+            keys: keyboard_rows(pools),
+            message: String::new(),
+            over: false,
+        };
+        for (guess_word, state_vec) in resumed.iter() {
+            tui.candidates = game::filter_candidates(&tui.candidates, guess_word, state_vec);
+            tui.board_state.insert(guess_word.clone(), state_vec.clone());
+        }
+        tui.history = resumed;
+        tui.suggest();
+        return tui;
+    }
+
+    // work out the suggestions for the board and type the best one in
+    fn suggest(&mut self) {
+        self.suggestions.clear();
+        self.choice = 0;
+        if self.candidates.is_empty() {
+            self.message = "No words fit the board. Check the colors, or the answer isn't in the list.".to_string();
+            self.type_word("");
+            return;
+        }
+        let best_word = player::best_guess(self.strategy, self.pools, &self.candidates, &self.board_state, self.past_answers, self.history.len());
+        let mut suggestions = player::top_guesses(self.strategy, self.pools, &self.candidates, TOP);
+        // the frequency strategy doesn't pick by score, so its guess might not be in the list
+        suggestions.retain(|(guess_word, _)| *guess_word != best_word);
+        let best_score = player::score(self.strategy, self.pools, &self.candidates, &best_word);
+        suggestions.insert(0, (best_word.clone(), best_score));
+        suggestions.truncate(TOP);
+        self.suggestions = suggestions;
+        self.type_word(&best_word);
+    }
+
+    fn type_word(&mut self, word: &str) {
+        self.letters = alphabet::letters(word).iter().map(|letter| letter.to_string()).collect();
+        self.colors = vec![0; self.letters.len()];
+        self.selected = 0;
+    }
+
+    fn key(&mut self, key: KeyEvent) -> bool {
+        if self.over {
+            return false;
+        }
+        let quit = matches!(key.code, KeyCode::Esc) || (key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL));
+        if quit {
+            return false;
+        }
+        match key.code {
+            KeyCode::Enter => self.play(),
+            KeyCode::Tab if !self.suggestions.is_empty() => {
+                self.choice = (self.choice + 1) % self.suggestions.len();
+                let word = self.suggestions[self.choice].0.clone();
+                self.type_word(&word);
+            }
+            KeyCode::Backspace => {
+                self.letters.pop();
+                self.colors.pop();
+                self.selected = self.selected.min(self.letters.len().saturating_sub(1));
+            }
+            KeyCode::Left => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Right => self.selected = (self.selected + 1).min(self.letters.len().saturating_sub(1)),
+            KeyCode::Char(' ') | KeyCode::Up => self.cycle(1),
+            KeyCode::Down => self.cycle(2),
+            KeyCode::Char(typed) if self.letters.len() < self.word_len => {
+                let letter = alphabet::normalize(&typed.to_string());
+                if self.keys.iter().flatten().any(|key| *key == letter) {
+                    self.letters.push(letter);
+                    self.colors.push(0);
+                    self.selected = self.letters.len() - 1;
+                }
+            }
+            _ => {}
+        }
+        return true;
+    }
+
+    // gray, yellow, green and round again
+    fn cycle(&mut self, step: u8) {
+        if let Some(color) = self.colors.get_mut(self.selected) {
+            *color = (*color + step) % 3;
+        }
+    }
+
+    fn play(&mut self) {
+        let guess_word: String = self.letters.concat();
+        if self.letters.len() != self.word_len {
+            self.message = format!("guesses need to be {} long.", self.word_len);
+            return;
+        }
+        if !self.pools.allowed.contains(guess_word.as_str()) {
+            self.message = format!("'{}' is not in the word list.", guess_word);
+            return;
+        }
+        let state_vec = self.colors.clone();
+        self.candidates = game::filter_candidates(&self.candidates, &guess_word, &state_vec);
+        self.history.push((guess_word.clone(), state_vec.clone()));
+        self.message.clear();
+        if game::is_solved(&state_vec) {
+            self.message = format!("Congratulations. solved in {}/{}, press any key.", self.history.len(), MAX_GUESSES);
+            self.over = true;
+        }
+        else if self.history.len() >= MAX_GUESSES {
+            self.message = "out of guesses, press any key.".to_string();
+            self.over = true;
+        }
+        else {
+            self.board_state.insert(guess_word, state_vec);
+            self.suggest();
+        }
+    }
+
+    fn draw(&self, frame: &mut Frame) {
+        let [main, status] = Layout::vertical([Constraint::Min(0), Constraint::Length(3)]).areas(frame.area());
+        let board_width = (self.word_len * 4 + 4).max(self.keys.iter().map(|row| row.len() * 3 + 6).max().unwrap_or(0)) as u16;
+        let [left, right] = Layout::horizontal([Constraint::Length(board_width), Constraint::Min(0)]).areas(main);
+        let [board_area, keyboard_area] = Layout::vertical([Constraint::Length(MAX_GUESSES as u16 + 2), Constraint::Min(0)]).areas(left);
+        let [suggestion_area, candidate_area] = Layout::vertical([Constraint::Length(TOP as u16 + 2), Constraint::Min(0)]).areas(right);
+
+        frame.render_widget(Paragraph::new(self.board_lines()).block(Block::default().borders(Borders::ALL).title(" board ")), board_area);
+        frame.render_widget(Paragraph::new(self.keyboard_lines()).block(Block::default().borders(Borders::ALL).title(" keyboard ")), keyboard_area);
+
+        let suggestions: Vec<Line> = self.suggestions.iter().enumerate().map(|(i, (guess_word, score))| {
+            let style = if i == self.choice { Style::default().add_modifier(Modifier::BOLD) } else { Style::default() };
+            Line::styled(format!("{:>2}. {}  {:.3}", i + 1, guess_word, score), style)
+        }).collect();
+        let title = format!(" top guesses, {} ", score_label(self.strategy));
+        frame.render_widget(Paragraph::new(suggestions).block(Block::default().borders(Borders::ALL).title(title)), suggestion_area);
+
+        let shown: Vec<&str> = self.candidates.iter().take(CANDIDATES_SHOWN).copied().collect();
+        let mut text = shown.join(" ");
+        if self.candidates.len() > CANDIDATES_SHOWN {
+            text.push_str(" ...");
+        }
+        let title = format!(" {} candidates ", self.candidates.len());
+        frame.render_widget(Paragraph::new(text).wrap(Wrap { trim: true }).block(Block::default().borders(Borders::ALL).title(title)), candidate_area);
+
+        let status_text = if self.message.is_empty() { HELP } else { self.message.as_str() };
+        frame.render_widget(Paragraph::new(status_text).wrap(Wrap { trim: true }).block(Block::default().borders(Borders::ALL)), status);
+    }
+
+    // the played rows, then the row being entered with its picked tile in brackets, then empty rows
+    fn board_lines(&self) -> Vec<Line<'_>> {
+        let mut lines: Vec<Line> = Vec::new();
+        for (guess_word, state_vec) in self.history.iter() {
+            let tiles = alphabet::letters(guess_word).iter().zip(state_vec.iter()).map(|(letter, state)| tile(letter, *state, false)).collect::<Vec<Span>>();
+            lines.push(Line::from(tiles));
+        }
+        if !self.over {
+            let mut tiles: Vec<Span> = self.letters.iter().zip(self.colors.iter()).enumerate()
+                .map(|(i, (letter, state))| tile(letter, *state, i == self.selected))
+                .collect();
+            tiles.extend((self.letters.len()..self.word_len).map(|_| Span::raw(" _ ")));
+            lines.push(Line::from(tiles));
+        }
+        while lines.len() < MAX_GUESSES {
+            lines.push(Line::from(" . ".repeat(self.word_len)));
+        }
+        return lines;
+    }
+
+    fn keyboard_lines(&self) -> Vec<Line<'_>> {
+        let states = render::letter_states(&self.history);
+        return self.keys.iter().enumerate().map(|(indent, row)| {
+            let mut keys: Vec<Span> = vec![Span::raw(" ".repeat(indent))];
+            for key in row.iter() {
+                match states.iter().find(|(letter, _)| letter == key) {
+                    Some((_, state)) => keys.push(tile(key, *state, false)),
+                    None => keys.push(Span::raw(format!(" {} ", key.to_uppercase()))),
+                }
+            }
+            Line::from(keys)
+        }).collect();
+    }
+}
+
+// what the numbers in the suggestion list mean
+fn score_label(strategy: Strategy) -> &'static str {
+    return match strategy {
+        Strategy::Frequency | Strategy::Entropy => "bits",
+        Strategy::ExpectedSize => "minus the words left on average",
+        Strategy::Minimax => "minus the words left at worst",
+    };
+}
+
+fn tile(letter: &str, state: u8, selected: bool) -> Span<'static> {
+    let background = match state {
+        2 => Color::Green,
+        1 => Color::Yellow,
+        _ => Color::DarkGray,
+    };
+    let foreground = if state == 0 { Color::White } else { Color::Black };
+    let text = if selected { format!("[{}]", letter.to_uppercase()) } else { format!(" {} ", letter.to_uppercase()) };
+    return Span::styled(text, Style::default().fg(foreground).bg(background).add_modifier(Modifier::BOLD));
+}
+
+// the qwerty rows with only the letters the words use, then a row for anything else like 'ñ' or nerdle's symbols
+fn keyboard_rows(pools: &Pools) -> Vec<Vec<String>> {
+    let mut used: HashSet<&str> = HashSet::new();
+    for word in pools.allowed.iter() {
+        used.extend(alphabet::letters(word));
+    }
+    let mut rows: Vec<Vec<String>> = Vec::new();
+    for row in render::KEYBOARD_ROWS.iter() {
+        let keys: Vec<String> = alphabet::letters(row).into_iter().filter(|letter| used.contains(letter)).map(|letter| letter.to_string()).collect();
+        if !keys.is_empty() {
+            rows.push(keys);
+        }
+    }
+    let mut extra: Vec<String> = used.iter().filter(|letter| !render::KEYBOARD_ROWS.iter().any(|row| row.contains(**letter))).map(|letter| letter.to_string()).collect();
+    extra.sort();
+    if !extra.is_empty() {
+        rows.push(extra);
+    }
+    return rows;
+}
+
+fn event_loop(terminal: &mut DefaultTerminal, tui: &mut Tui, save: impl Fn(&[(String,Vec<u8>)])) -> Result<(), String> {
+    loop {
+        terminal.draw(|frame| tui.draw(frame)).map_err(|error| format!("Could not draw the screen: {}", error))?;
+        let played = tui.history.len();
+        match event::read().map_err(|error| format!("Could not read a key: {}", error))? {
+            // key returns false once the game is done with
+            Event::Key(key) if key.kind == KeyEventKind::Press && !tui.key(key) => return Ok(()),
+            _ => {}
+        }
+        if tui.history.len() != played {
+            save(&tui.history);
+        }
+    }
+}
+
+// play a game on the full screen board, carrying on from any resumed guesses. save is given the history after
+// every guess. returns every guess and its results like interactive does, and whether the game was finished
+pub fn play(pools: &Pools, past_answers: &[&str], strategy: Strategy, resumed: History, save: impl Fn(&[(String,Vec<u8>)])) -> Result<(History, bool), String> {
+    let mut tui = Tui::new(pools, past_answers, strategy, resumed);
+    let mut terminal = ratatui::try_init().map_err(|error| format!("Could not start the full screen board: {}", error))?;
+    let result = event_loop(&mut terminal, &mut tui, save);
+    ratatui::restore();
+    return result.map(|_| (tui.history, tui.over));
+}
@@ -0,0 +1,129 @@
+/*
+* loads word lists from files. every line is cleaned up the same way: surrounding whitespace and
+* windows line endings are trimmed, letters are lowercased and blank lines are skipped.
+* lines that aren't a 5 letter word, or repeat an earlier word, are reported with their line number.
+* lines starting with '#' are comments, except '#alphabet' which lists the letters words can use,
+* like '#alphabet abcdefghijklmnñopqrstuvwxyz' for spanish. lists without one are english.
+* the lists in words/ are built into the binary as defaults unless the embedded-words feature is off.
+*/
+
+use std::collections::HashSet;
+use std::fs;
+
+use crate::alphabet;
+use crate::alphabet::Alphabet;
+use crate::output;
+
+pub const WORD_LEN: usize = 5;
+
+const ALPHABET_DIRECTIVE: &str = "#alphabet";
+
+#[cfg(feature = "embedded-words")]
+pub const EMBEDDED_WORDS: Option<&str> = Some(include_str!("../../words/wordle_words.txt"));
+#[cfg(not(feature = "embedded-words"))]
+pub const EMBEDDED_WORDS: Option<&str> = None;
+
+#[cfg(feature = "embedded-words")]
+pub const EMBEDDED_ANSWERS: Option<&str> = Some(include_str!("../../words/ny_times_answers.txt"));
+#[cfg(not(feature = "embedded-words"))]
+pub const EMBEDDED_ANSWERS: Option<&str> = None;
+
+// what to do with a word that's already in the list
+#[derive(Clone, Copy)]
+pub enum Duplicates {
+    // allowed guesses, a second copy adds nothing
+    Drop,
+    // the answer list, where line n is puzzle n, so a repeated answer is still a day
+    Keep,
+}
+
+// a line that was skipped or looks wrong
+pub struct Problem {
+    pub line: usize,
+    pub text: String,
+    pub reason: String,
+}
+
+pub struct WordList {
+    // where the list came from, for warnings
+    pub source: String,
+    // the letters its words can use
+    pub alphabet: Alphabet,
+    // in file order
+    pub words: Vec<String>,
+    pub problems: Vec<Problem>,
+}
+
+// default_alphabet is used when the file doesn't say, the answers go with whatever the guesses use
+pub fn load(path: &str, duplicates: Duplicates, default_alphabet: &Alphabet) -> Result<WordList, String> {
+    return match fs::read_to_string(path) {
+        Ok(text) => Ok(parse(path, &text, duplicates, default_alphabet)),
+        Err(error) => Err(format!("Could not open word file '{}': {}", path, error)),
+    };
+}
+
+// load the file if one was given, otherwise fall back to the built in list
+pub fn load_or_embedded(path: Option<&str>, embedded: Option<&str>, duplicates: Duplicates, default_alphabet: &Alphabet) -> Result<WordList, String> {
+    return match (path, embedded) {
+        (Some(path), _) => load(path, duplicates, default_alphabet),
+        (None, Some(text)) => Ok(parse("built in list", text, duplicates, default_alphabet)),
+        (None, None) => Err("This build has no built in word lists, pass them with --words and --answers".to_string()),
+    };
+}
+
+// a word the way every list writes it, without the whitespace or line ending around it
+pub fn clean(raw: &str) -> String {
+    return alphabet::normalize(raw.trim());
+}
+
+pub fn parse(source: &str, text: &str, duplicates: Duplicates, default_alphabet: &Alphabet) -> WordList {
+    let mut words: Vec<String> = Vec::new();
+    let mut seen: HashSet<String> = HashSet::new();
+    let mut problems: Vec<Problem> = Vec::new();
+    let alphabet = text.lines()
+        .find_map(|line| line.trim().strip_prefix(ALPHABET_DIRECTIVE))
+        .map(|letters| Alphabet::parse(letters.trim_start_matches(':')))
+        .unwrap_or_else(|| default_alphabet.clone());
+
+    for (i, raw) in text.lines().enumerate() {
+        let word = clean(raw);
+        if word.is_empty() || word.starts_with('#') {
+            continue
+        }
+        let problem = |reason: String| Problem { line: i + 1, text: raw.to_string(), reason };
+
+        let unknown = alphabet.unknown_letters(&word);
+        if !unknown.is_empty() {
+            problems.push(problem(format!("has letters that aren't in the alphabet: {}", unknown.join(" "))));
+            continue
+        }
+        let length = alphabet::letter_count(&word);
+        if length != WORD_LEN {
+            problems.push(problem(format!("is {} letters long, not {}", length, WORD_LEN)));
+            continue
+        }
+        if !seen.insert(word.clone()) {
+            match duplicates {
+                Duplicates::Drop => {
+                    problems.push(problem("is a duplicate, skipped".to_string()));
+                    continue
+                }
+                Duplicates::Keep => problems.push(problem("is a duplicate".to_string())),
+            }
+        }
+        words.push(word);
+    }
+
+    return WordList { source: source.to_string(), alphabet, words, problems };
+}
+
+// print what was wrong with a list. only the first few lines, a broken file could have thousands
+pub fn report(list: &WordList) {
+    const SHOWN: usize = 10;
+    for problem in list.problems.iter().take(SHOWN) {
+        output::say(&format!("warning: {} line {}: '{}' {}", list.source, problem.line, problem.text, problem.reason));
+    }
+    if list.problems.len() > SHOWN {
+        output::say(&format!("warning: {} has {} more problems", list.source, list.problems.len() - SHOWN));
+    }
+}
@@ -0,0 +1,108 @@
+/*
+* the solver against the real word lists. properties that have to hold for any answer and guess,
+* and the benchmark's distribution pinned so a change to the heuristic shows up as a failing test.
+*/
+
+#![allow(clippy::needless_return)]
+
+use std::collections::HashMap;
+
+use proptest::prelude::*;
+
+use wordlehelper::alphabet::Alphabet;
+use wordlehelper::automated;
+use wordlehelper::game;
+use wordlehelper::game::{Feedback, Pools};
+use wordlehelper::player;
+use wordlehelper::player::Strategy;
+use wordlehelper::prior::Prior;
+use wordlehelper::wordlist;
+use wordlehelper::wordlist::{Duplicates, WordList};
+
+const WORDS: &str = include_str!("../../words/wordle_words.txt");
+const ANSWERS: &str = include_str!("../../words/ny_times_answers.txt");
+
+// how many games took each number of guesses over the whole answer list, from 1 guess to 12. none fail
+const DISTRIBUTION: [usize; 12] = [0, 41, 417, 746, 589, 291, 141, 50, 24, 5, 3, 1];
+
+fn lists() -> (WordList, WordList) {
+    let word_list = wordlist::parse("words", WORDS, Duplicates::Drop, &Alphabet::english());
+    let answer_list = wordlist::parse("answers", ANSWERS, Duplicates::Keep, &word_list.alphabet);
+    return (word_list, answer_list);
+}
+
+#[test]
+fn answer_is_never_filtered_out() {
+    let (word_list, answer_list) = lists();
+    let words: Vec<&str> = word_list.words.iter().map(|word| word.as_str()).collect();
+    let answers: Vec<&str> = answer_list.words.iter().map(|word| word.as_str()).collect();
+    let feedbacks = [Feedback::Colors, Feedback::CommonLetters, Feedback::Pegs];
+    proptest!(|(day in 0..answers.len(), guesses in prop::collection::vec(0..words.len(), 1..6))| {
+        let answer = answers[day];
+        for feedback in feedbacks {
+            let mut candidates = answers.clone();
+            for guess_word in guesses.iter().map(|index| words[*index]) {
+                let results = feedback.results(answer, guess_word);
+                candidates = game::filter_by_feedback(feedback, &candidates, guess_word, &results);
+                prop_assert!(candidates.contains(&answer), "{} filtered out by {} with {:?}", answer, guess_word, feedback.name());
+            }
+        }
+    });
+}
+
+#[test]
+fn suggestions_are_in_the_word_list() {
+    let (word_list, answer_list) = lists();
+    let words: Vec<&str> = word_list.words.iter().map(|word| word.as_str()).collect();
+    let answers: Vec<&str> = answer_list.words.iter().map(|word| word.as_str()).collect();
+    let pools = Pools::new(&words, &answers, Prior::uniform(), Feedback::Colors);
+    proptest!(|(day in 0..answers.len(), guesses in prop::collection::vec(0..words.len(), 1..4))| {
+        let answer = answers[day];
+        let guesses: Vec<&str> = guesses.iter().map(|index| words[*index]).collect();
+        let mut board_state: HashMap<String,Vec<u8>> = HashMap::new();
+        let mut candidates = answers.clone();
+        for guess_word in guesses.iter() {
+            let state_vec = game::determine_board_results(answer, guess_word);
+            candidates = game::filter_candidates(&candidates, guess_word, &state_vec);
+            board_state.insert(guess_word.to_string(), state_vec);
+        }
+        let suggestion = player::best_guess(Strategy::Frequency, &pools, &candidates, &board_state, &[], guesses.len());
+        prop_assert!(pools.allowed.contains(suggestion.as_str()), "suggested {:?} after {:?}", suggestion, guesses);
+    });
+}
+
+#[test]
+fn every_answer_game_terminates() {
+    let (word_list, answer_list) = lists();
+    let words: Vec<&str> = word_list.words.iter().map(|word| word.as_str()).collect();
+    let answers: Vec<&str> = answer_list.words.iter().map(|word| word.as_str()).collect();
+    let pools = Pools::new(&words, &answers, Prior::uniform(), Feedback::Colors);
+    proptest!(|(day in 0..answers.len())| {
+        let history = automated::play(&pools, &answers, day, Strategy::Frequency, &mut None, true);
+        prop_assert_eq!(history.last().map(|(guess_word, _)| guess_word.as_str()), Some(answers[day]));
+        for (guess_word, _) in history.iter() {
+            prop_assert!(pools.allowed.contains(guess_word.as_str()));
+        }
+    });
+}
+
+#[test]
+fn benchmark_distribution() {
+    let (word_list, answer_list) = lists();
+    let words: Vec<&str> = word_list.words.iter().map(|word| word.as_str()).collect();
+    let answers: Vec<&str> = answer_list.words.iter().map(|word| word.as_str()).collect();
+    let pools = Pools::new(&words, &answers, Prior::uniform(), Feedback::Colors);
+    let mut opener = None;
+    let mut distribution = vec![0; DISTRIBUTION.len()];
+    let mut failed: Vec<usize> = Vec::new();
+    for day in 0..answers.len() {
+        let history = automated::play(&pools, &answers, day, Strategy::Frequency, &mut opener, true);
+        match history.last() {
+            Some((guess_word, _)) if guess_word == answers[day] && history.len() <= DISTRIBUTION.len() => distribution[history.len() - 1] += 1,
+            _ => failed.push(day),
+        }
+    }
+    assert_eq!(answers.len(), 2308);
+    assert_eq!(failed, Vec::<usize>::new());
+    assert_eq!(distribution, DISTRIBUTION);
+}